@@ -0,0 +1,40 @@
+// Shards encrypted to custodians' age recipients (X25519 or SSH keys), readable
+// with the `age` and `rage` command-line tools
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use age::{DecryptError, Decryptor, Encryptor, Identity, Recipient};
+use std::io::{Read, Write};
+use std::iter;
+use zeroize::Zeroizing;
+
+// Binary age file holding `Shard::to_bytes`, decryptable by any of `recipients`
+pub fn encrypt_shard_to_recipients(shard: &Shard, recipients: &[Box<dyn Recipient>])
+    -> Result<Vec<u8>, TimelockError>
+{
+    let encryptor = Encryptor::with_recipients(recipients.iter().map(|recipient| recipient.as_ref()))
+        .map_err(|e| TimelockError::InvalidConfiguration(format!("age encryption failed: {}", e)))?;
+
+    let plaintext = Zeroizing::new(shard.to_bytes());
+    let mut ciphertext = Vec::new();
+    let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+    writer.write_all(&plaintext)?;
+    writer.finish()?;
+    Ok(ciphertext)
+}
+
+pub fn decrypt_shard(ciphertext: &[u8], identity: &dyn Identity) -> Result<Shard, TimelockError> {
+    let decryptor = Decryptor::new(ciphertext).map_err(age_error)?;
+    let mut reader = decryptor.decrypt(iter::once(identity)).map_err(age_error)?;
+
+    let mut plaintext = Zeroizing::new(Vec::new());
+    reader.read_to_end(&mut plaintext).map_err(|_| TimelockError::DecryptionFailed)?;
+    Shard::from_bytes(&plaintext)
+}
+
+fn age_error(e: DecryptError) -> TimelockError {
+    match e {
+        DecryptError::DecryptionFailed | DecryptError::NoMatchingKeys => TimelockError::DecryptionFailed,
+        e => TimelockError::InvalidShardFormat(format!("not a readable age file: {}", e)),
+    }
+}
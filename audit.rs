@@ -0,0 +1,64 @@
+// Audit trail of shard issuance, reconstruction and entropy checks
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Timestamps are seconds since the Unix epoch
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuditEvent {
+    ShardCreated { index: u8, timestamp: u64 },
+    ReconstructionAttempted { num_shards: usize, success: bool, timestamp: u64 },
+    // `entropy` is the shard's score under the configured `EntropyMeasure`
+    EntropyCheckFailed { index: u8, entropy: f64, timestamp: u64 },
+}
+
+impl AuditEvent {
+    // One JSON object, e.g. {"event":"ShardCreated","index":1,"timestamp":1700000000}
+    pub fn to_json(&self) -> String {
+        match self {
+            AuditEvent::ShardCreated { index, timestamp } => format!(
+                r#"{{"event":"ShardCreated","index":{},"timestamp":{}}}"#, index, timestamp),
+            AuditEvent::ReconstructionAttempted { num_shards, success, timestamp } => format!(
+                r#"{{"event":"ReconstructionAttempted","num_shards":{},"success":{},"timestamp":{}}}"#,
+                num_shards, success, timestamp),
+            AuditEvent::EntropyCheckFailed { index, entropy, timestamp } => format!(
+                r#"{{"event":"EntropyCheckFailed","index":{},"entropy":{},"timestamp":{}}}"#,
+                index, entropy, timestamp),
+        }
+    }
+}
+
+pub trait AuditLog: fmt::Debug + Send + Sync {
+    fn record(&self, event: AuditEvent);
+}
+
+// Appends one JSON line per event to a file
+#[derive(Debug)]
+pub struct FileAuditLog {
+    file: Mutex<File>,
+}
+
+impl FileAuditLog {
+    // Open `path` for appending, creating it if absent
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileAuditLog { file: Mutex::new(file) })
+    }
+}
+
+impl AuditLog for FileAuditLog {
+    // `record` cannot fail, so a write error loses the event rather than the operation
+    fn record(&self, event: AuditEvent) {
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _ = writeln!(file, "{}", event.to_json());
+    }
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
@@ -0,0 +1,77 @@
+// Entropy audit of many shard files at once, one rayon task per file
+
+use crate::error::TimelockError;
+use crate::mnemonic::MnemonicShard;
+use crate::shard::Shard;
+use crate::{entropy, ShardEntropyReport, ShardMetrics, TimelockKeySharding};
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Read, decode and entropy-check every file in parallel, with the default
+// entropy thresholds. A file holds one shard as JSON, PEM, mnemonic words or
+// raw `to_bytes` output. Files that cannot be read or decoded do not stop the
+// batch: their report holds a single failed `ShardMetrics` whose `read_error`
+// says why. Results are in the order of `shard_paths`.
+pub fn batch_entropy_check<P>(shard_paths: &[P]) -> Vec<(PathBuf, ShardEntropyReport)>
+where
+    P: AsRef<Path> + Sync,
+{
+    shard_paths.par_iter()
+        .map(|path| {
+            let path = path.as_ref();
+            let report = match read_shard_file(path) {
+                // Difficulty and threshold play no part in the entropy checks
+                Ok(shard) => TimelockKeySharding::new(0, shard.threshold as usize)
+                    .check_shard_entropy(std::slice::from_ref(&shard)),
+                Err(e) => ShardEntropyReport { passed: false, per_shard: vec![read_failure(e.to_string())] },
+            };
+            (path.to_path_buf(), report)
+        })
+        .collect()
+}
+
+fn read_shard_file(path: &Path) -> Result<Shard, TimelockError> {
+    let bytes = fs::read(path)?;
+    let Ok(text) = std::str::from_utf8(&bytes) else {
+        return Shard::from_bytes(&bytes);
+    };
+
+    let text = text.trim_start();
+    if text.starts_with('{') {
+        json_shard(text)
+    } else if text.starts_with("-----BEGIN") {
+        Shard::from_pem(text)
+    } else {
+        text.parse::<MnemonicShard>()?.to_shard()
+    }
+}
+
+#[cfg(feature = "serde")]
+fn json_shard(text: &str) -> Result<Shard, TimelockError> {
+    serde_json::from_str(text).map_err(|e| TimelockError::InvalidShardFormat(e.to_string()))
+}
+
+#[cfg(not(feature = "serde"))]
+fn json_shard(_text: &str) -> Result<Shard, TimelockError> {
+    Err(TimelockError::InvalidShardFormat("JSON shards need the `serde` feature".to_string()))
+}
+
+fn read_failure(error: String) -> ShardMetrics {
+    ShardMetrics {
+        index: 0,
+        shannon_entropy: 0.0,
+        min_entropy: 0.0,
+        collision_entropy: 0.0,
+        borel_regularity_ratio: 0.0,
+        runs_ratio: 0.0,
+        chi_squared_stat: 0.0,
+        spectral: entropy::spectral_test(&[]),
+        linear_complexity: 0,
+        passed_entropy: false,
+        passed_borel: false,
+        passed_runs: false,
+        chi_squared_passed: false,
+        read_error: Some(error),
+    }
+}
@@ -0,0 +1,23 @@
+// Compare sequential and parallel shard generation:
+//
+//     cargo bench --bench shard_generation
+//     cargo bench --bench shard_generation --features parallel
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::LCS35;
+
+fn shard_generation(c: &mut Criterion) {
+    let mode = if cfg!(feature = "parallel") { "parallel" } else { "sequential" };
+    let puzzle = LCS35::new(20);
+    let key = [0x5au8; 32];
+    let mut rng = ChaChaRng::from_seed([0u8; 32]);
+
+    c.bench_function(&format!("shard_100_difficulty_20_{}", mode), |b| {
+        b.iter(|| puzzle.shard(black_box(&key), 100, 3, &mut rng).unwrap())
+    });
+}
+
+criterion_group!(benches, shard_generation);
+criterion_main!(benches);
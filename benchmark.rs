@@ -0,0 +1,57 @@
+// Saved squaring benchmarks, so a difficulty can be chosen from a measured rate
+// without re-running the benchmark
+//
+// `schrödinger bench` writes one to `~/.schrödinger/benchmark.json`; a TOML
+// config with `calibrate_difficulty = true` reads it back.
+
+use crate::audit;
+use crate::error::TimelockError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub squarings_per_second: u64,
+    // Wall-clock length of the run
+    pub duration_secs: f64,
+    pub modulus_bits: usize,
+    // Unix seconds at which the run finished
+    pub measured_at: u64,
+}
+
+impl BenchmarkResult {
+    pub fn new(squarings_per_second: u64, duration: Duration, modulus_bits: usize) -> Self {
+        BenchmarkResult {
+            squarings_per_second,
+            duration_secs: duration.as_secs_f64(),
+            modulus_bits,
+            measured_at: audit::now(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, TimelockError> {
+        serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| TimelockError::InvalidConfiguration(format!("{}: {}", path.display(), e)))
+    }
+
+    // Creates the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), TimelockError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| TimelockError::InvalidConfiguration(e.to_string()))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+// `~/.schrödinger/benchmark.json`, or `None` when no home directory is set
+pub fn default_benchmark_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".schrödinger").join("benchmark.json"))
+}
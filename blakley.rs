@@ -0,0 +1,187 @@
+// Blakley's geometric secret sharing over GF(p)
+//
+// The key is cut into chunks that fit below p, and each chunk becomes the first
+// coordinate of a secret point whose other `threshold - 1` coordinates are random.
+// Every shard holds one random hyperplane a . x = b through each secret point;
+// any `threshold` of them intersect in exactly that point. Shards are not
+// timelocked.
+//
+// Share layout after the x byte, all integers u32 LE:
+//   p, key length, then per chunk a_1 .. a_threshold, b
+
+use crate::error::TimelockError;
+use crate::secret_splitter::{check_split_params, distinct_shares, plain_shard, SecretSplitter};
+use crate::shard::Shard;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+// 2^31 - 1, a Mersenne prime, so each element carries 3 key bytes
+pub const DEFAULT_BLAKLEY_FIELD: usize = 2_147_483_647;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BlakleySplitter {
+    // The prime p, between 257 and 2^32
+    pub field_size: usize,
+}
+
+impl Default for BlakleySplitter {
+    fn default() -> Self {
+        BlakleySplitter { field_size: DEFAULT_BLAKLEY_FIELD }
+    }
+}
+
+impl BlakleySplitter {
+    pub fn new(field_size: usize) -> Self {
+        BlakleySplitter { field_size }
+    }
+
+    fn prime(&self) -> Result<u64, TimelockError> {
+        let p = self.field_size as u64;
+        if !(257..=u32::MAX as u64).contains(&p) || !is_prime(p) {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "Blakley field size must be a prime between 257 and 2^32, got {}", self.field_size)));
+        }
+        Ok(p)
+    }
+}
+
+impl SecretSplitter for BlakleySplitter {
+    // A random set of hyperplanes is degenerate with probability about 1/p, so
+    // small fields can occasionally produce shards `combine` cannot solve
+    fn split(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut dyn RngCore)
+        -> Result<Vec<Shard>, TimelockError>
+    {
+        check_split_params(num_shards, threshold)?;
+        let p = self.prime()?;
+        let chunk_len = chunk_len(p);
+
+        let points: Vec<Zeroizing<Vec<u64>>> = key.chunks(chunk_len)
+            .map(|chunk| {
+                let mut point = Zeroizing::new(vec![chunk_value(chunk)]);
+                point.extend((1..threshold).map(|_| random_element(rng, p)));
+                point
+            })
+            .collect();
+
+        Ok((1..=num_shards as u8)
+            .map(|x| {
+                let mut share = Vec::with_capacity(8 + points.len() * (threshold + 1) * 4);
+                share.extend_from_slice(&(p as u32).to_le_bytes());
+                share.extend_from_slice(&(key.len() as u32).to_le_bytes());
+                for point in &points {
+                    let normal: Vec<u64> = (0..threshold).map(|_| random_element(rng, p)).collect();
+                    let b = normal.iter().zip(point.iter()).fold(0, |acc, (a, c)| (acc + a * c % p) % p);
+                    for a in normal {
+                        share.extend_from_slice(&(a as u32).to_le_bytes());
+                    }
+                    share.extend_from_slice(&(b as u32).to_le_bytes());
+                }
+                plain_shard(x, num_shards, threshold, &share)
+            })
+            .collect())
+    }
+
+    fn combine(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        let p = self.prime()?;
+        let shares = distinct_shares(shards)?;
+        let threshold = (shards[0].threshold as usize).max(1);
+        if shares.len() < threshold {
+            return Err(TimelockError::InsufficientShards { provided: shares.len(), required: threshold });
+        }
+
+        let chunk_len = chunk_len(p);
+        let mut key_len = 0;
+        let mut planes = Vec::with_capacity(threshold);
+        for (index, (_, share)) in shares.iter().take(threshold).enumerate() {
+            let words: Vec<u64> = share.chunks(4)
+                .map(|word| word.try_into().map(|word| u32::from_le_bytes(word) as u64))
+                .collect::<Result<_, _>>()
+                .map_err(|_| TimelockError::CorruptedShard { index })?;
+            if words.len() < 2 || words[0] != p {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            key_len = words[1] as usize;
+            let rows = &words[2..];
+            if rows.len() != key_len.div_ceil(chunk_len) * (threshold + 1) || rows.iter().any(|&v| v >= p) {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            planes.push(rows.to_vec());
+        }
+
+        let mut key = Zeroizing::new(Vec::with_capacity(key_len));
+        let mut system = Zeroizing::new(Vec::with_capacity(threshold * (threshold + 1)));
+        for chunk in 0..key_len.div_ceil(chunk_len) {
+            system.clear();
+            for rows in &planes {
+                system.extend_from_slice(&rows[chunk * (threshold + 1)..(chunk + 1) * (threshold + 1)]);
+            }
+            let value = solve_first(&mut system, threshold, p).ok_or_else(|| TimelockError::PuzzleError(
+                "Blakley hyperplanes do not meet in a single point".to_string()))?;
+
+            let this_len = chunk_len.min(key_len - chunk * chunk_len);
+            if value >> (8 * this_len) != 0 {
+                return Err(TimelockError::PuzzleError(
+                    "reconstructed chunk does not fit the recorded key length".to_string()));
+            }
+            key.extend((0..this_len).rev().map(|i| (value >> (8 * i)) as u8));
+        }
+        Ok(key.to_vec())
+    }
+}
+
+// Key bytes per field element, so every chunk is below p
+fn chunk_len(p: u64) -> usize {
+    ((64 - p.leading_zeros() as usize - 1) / 8).max(1)
+}
+
+fn chunk_value(chunk: &[u8]) -> u64 {
+    chunk.iter().fold(0, |acc, &byte| acc << 8 | byte as u64)
+}
+
+// Bias from the reduction is below 2^-32 for p < 2^32
+fn random_element(rng: &mut dyn RngCore, p: u64) -> u64 {
+    rng.next_u64() % p
+}
+
+// Gaussian elimination mod p on an n x (n + 1) augmented matrix, returning the
+// first coordinate of the unique solution
+fn solve_first(matrix: &mut [u64], n: usize, p: u64) -> Option<u64> {
+    let width = n + 1;
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| matrix[row * width + column] != 0)?;
+        for k in 0..width {
+            matrix.swap(column * width + k, pivot * width + k);
+        }
+
+        let inverse = pow_mod(matrix[column * width + column], p - 2, p);
+        for k in 0..width {
+            matrix[column * width + k] = matrix[column * width + k] * inverse % p;
+        }
+        for row in (0..n).filter(|&row| row != column) {
+            let factor = matrix[row * width + column];
+            for k in 0..width {
+                let scaled = factor * matrix[column * width + k] % p;
+                matrix[row * width + k] = (matrix[row * width + k] + p - scaled) % p;
+            }
+        }
+    }
+    Some(matrix[n])
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, p: u64) -> u64 {
+    let mut result = 1;
+    base %= p;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % p;
+        }
+        base = base * base % p;
+        exponent >>= 1;
+    }
+    result
+}
+
+// Trial division is enough for p < 2^32
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
@@ -0,0 +1,15 @@
+// Generates the gRPC service code for the `grpc` feature
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/schrödinger.proto");
+        // Use a system protoc when one is configured, the vendored one otherwise
+        if std::env::var_os("PROTOC").is_none() {
+            let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+            std::env::set_var("PROTOC", protoc);
+        }
+        tonic_build::compile_protos("proto/schrödinger.proto")
+            .expect("proto/schrödinger.proto should compile");
+    }
+}
@@ -0,0 +1,58 @@
+// Compressed multi-shard bundles for handing a whole shard set to one party
+//
+// Layout: magic "SCHR", format version (u8), then a gzip stream of a CBOR array
+// holding each shard in the `Shard::to_cbor` map form.
+
+use crate::cbor;
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use ciborium::value::Value;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 4] = b"SCHR";
+pub const BUNDLE_VERSION: u8 = 1;
+
+// Upper bound on the decompressed CBOR, so a hostile bundle cannot exhaust memory
+const MAX_UNPACKED_LEN: u64 = 64 * 1024 * 1024;
+
+pub fn pack_shards(shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+    let array = Value::Array(shards.iter().map(cbor::to_value).collect());
+    let mut encoded = Vec::new();
+    ciborium::ser::into_writer(&array, &mut encoded)
+        .expect("writing CBOR to a Vec cannot fail");
+
+    let mut bundle = MAGIC.to_vec();
+    bundle.push(BUNDLE_VERSION);
+    let mut gzip = GzEncoder::new(bundle, Compression::default());
+    gzip.write_all(&encoded)?;
+    Ok(gzip.finish()?)
+}
+
+pub fn unpack_shards(data: &[u8]) -> Result<Vec<Shard>, TimelockError> {
+    let body = data.strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| TimelockError::InvalidShardFormat("not a shard bundle".to_string()))?;
+    let (&version, compressed) = body.split_first()
+        .ok_or_else(|| TimelockError::InvalidShardFormat("shard bundle is truncated".to_string()))?;
+    if version != BUNDLE_VERSION {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "unsupported shard bundle version {}, expected {}", version, BUNDLE_VERSION)));
+    }
+
+    let mut encoded = Vec::new();
+    GzDecoder::new(compressed).take(MAX_UNPACKED_LEN + 1).read_to_end(&mut encoded)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("corrupt shard bundle: {}", e)))?;
+    if encoded.len() as u64 > MAX_UNPACKED_LEN {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "shard bundle expands past {} bytes", MAX_UNPACKED_LEN)));
+    }
+
+    let value: Value = ciborium::de::from_reader(encoded.as_slice())
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("not valid CBOR: {}", e)))?;
+    let Value::Array(items) = value else {
+        return Err(TimelockError::InvalidShardFormat("shard bundle does not hold an array".to_string()));
+    };
+    items.into_iter().map(cbor::from_value).collect()
+}
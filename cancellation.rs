@@ -0,0 +1,29 @@
+// Cooperative cancellation for long-running puzzle solving
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// Cheap to clone; every clone observes the same flag, so one can be handed to a
+// UI thread or signal handler while another is passed to `LCS35::unlock_with_progress`
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        CancellationToken(flag)
+    }
+}
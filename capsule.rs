@@ -0,0 +1,129 @@
+// Self-contained archive of a timelocked key: the shards encrypted to their
+// custodians, the puzzle's public parameters and creation metadata
+//
+// Each shard is encrypted to one custodian's X25519 public key: the AES-256-GCM
+// key of its `EncryptedShard` is SHA-256 over a context string, the X25519
+// shared secret between the capsule's ephemeral key and the custodian's key, and
+// both public keys.
+
+use crate::audit;
+use crate::difficulty::Difficulty;
+use crate::encrypted_shard::EncryptedShard;
+use crate::error::TimelockError;
+use crate::puzzle_params::PuzzleParams;
+use crate::shard::Shard;
+use crate::silurian_puzzle;
+use crate::threshold_proof::{ThresholdProof, THRESHOLD_SALT_LEN};
+use crate::TimelockKeySharding;
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+const CAPSULE_KEY_CONTEXT: &[u8] = b"schrodinger capsule shard key";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimelockCapsule {
+    // Seconds since the Unix epoch
+    pub created_at: u64,
+    pub creator_id: String,
+    // Modulus and iteration count shared by every shard's puzzle; each shard
+    // carries its own initial value
+    pub puzzle_params: PuzzleParams,
+    pub threshold: u8,
+    // Commitment to `threshold` made at the ceremony, checked with `verify_threshold`
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub threshold_proof: Option<ThresholdProof>,
+    // Public half of the key the shard encryption keys were agreed with
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub ephemeral_public_key: [u8; 32],
+    // One shard per recipient key, in the order the keys were given
+    pub encrypted_shards: Vec<EncryptedShard>,
+    pub notes: String,
+}
+
+impl TimelockCapsule {
+    // Shard `key` under `config` into one shard per recipient and encrypt each
+    // shard to its recipient
+    pub fn seal(key: &[u8], config: &TimelockKeySharding, recipient_keys: &[PublicKey], notes: &str)
+        -> Result<TimelockCapsule, TimelockError>
+    {
+        if recipient_keys.len() < config.threshold {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "{} recipients cannot meet a threshold of {}", recipient_keys.len(), config.threshold)));
+        }
+        if recipient_keys.len() < 2 || recipient_keys.len() > u8::MAX as usize {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "number of recipients must be between 2 and 255, got {}", recipient_keys.len())));
+        }
+
+        let shards = config.shard_bytes(key, recipient_keys.len())?;
+        let ephemeral = StaticSecret::random_from_rng(rand::thread_rng());
+        let ephemeral_public = PublicKey::from(&ephemeral);
+
+        let encrypted_shards = shards.iter()
+            .zip(recipient_keys)
+            .map(|(shard, recipient)| shard.encrypt(&shard_key(&ephemeral, &ephemeral_public, recipient)))
+            .collect();
+
+        let modulus = shards.first()
+            .and_then(|shard| silurian_puzzle::locked_puzzle(&shard.payload))
+            .map(|(modulus, _)| hex::encode(modulus.to_bytes_be()));
+
+        Ok(TimelockCapsule {
+            created_at: audit::now(),
+            creator_id: String::new(),
+            puzzle_params: PuzzleParams {
+                difficulty: config.difficulty,
+                iterations: Difficulty(config.difficulty).iterations(),
+                modulus_hex: modulus,
+                initial_value_hex: None,
+            },
+            threshold: config.threshold as u8,
+            threshold_proof: None,
+            ephemeral_public_key: ephemeral_public.to_bytes(),
+            encrypted_shards,
+            notes: notes.to_string(),
+        })
+    }
+
+    pub fn with_creator_id(mut self, creator_id: impl Into<String>) -> Self {
+        self.creator_id = creator_id.into();
+        self
+    }
+
+    pub fn with_threshold_proof(mut self, proof: ThresholdProof) -> Self {
+        self.threshold_proof = Some(proof);
+        self
+    }
+
+    // Whether the coordinator's revealed `salt` opens the threshold proof to this
+    // capsule's `threshold`; false for a capsule without a proof
+    pub fn verify_threshold(&self, salt: &[u8; THRESHOLD_SALT_LEN]) -> bool {
+        self.threshold_proof.is_some_and(|proof| proof.verify(self.threshold, salt))
+    }
+
+    // Decrypt the shard sealed to the custodian holding `secret`
+    pub fn open_shard(&self, secret: &StaticSecret) -> Result<Shard, TimelockError> {
+        let ephemeral_public = PublicKey::from(self.ephemeral_public_key);
+        let key = shard_key(secret, &ephemeral_public, &PublicKey::from(secret));
+        self.encrypted_shards.iter()
+            .find_map(|encrypted| encrypted.decrypt(&key).ok())
+            .ok_or(TimelockError::DecryptionFailed)
+    }
+}
+
+// Both sides compute the same shared secret: the sealer from the ephemeral secret
+// and the recipient's public key, the recipient from their secret and the
+// ephemeral public key
+fn shard_key(secret: &StaticSecret, ephemeral_public: &PublicKey, recipient: &PublicKey) -> [u8; 32] {
+    let other = if PublicKey::from(secret) == *ephemeral_public { recipient } else { ephemeral_public };
+    let shared = Zeroizing::new(secret.diffie_hellman(other).to_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(CAPSULE_KEY_CONTEXT);
+    hasher.update(shared.as_slice());
+    hasher.update(ephemeral_public.as_bytes());
+    hasher.update(recipient.as_bytes());
+    hasher.finalize().into()
+}
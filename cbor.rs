@@ -0,0 +1,99 @@
+// CBOR encoding of shards for compact binary storage
+//
+// A shard is a CBOR map keyed by its field names, with `payload` and `mac` as
+// byte strings rather than the hex text used by the JSON form. `mac` and the
+// maturity clock's `release_at` are left out when the shard carries none.
+
+use crate::error::TimelockError;
+use crate::maturity::MaturityClock;
+use crate::shard::{Shard, MAC_LEN};
+use ciborium::value::{Integer, Value};
+
+pub(crate) fn encode(shard: &Shard) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&to_value(shard), &mut bytes)
+        .expect("writing CBOR to a Vec cannot fail");
+    bytes
+}
+
+pub(crate) fn decode(b: &[u8]) -> Result<Shard, TimelockError> {
+    let value: Value = ciborium::de::from_reader(b)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("not valid CBOR: {}", e)))?;
+    from_value(value)
+}
+
+pub(crate) fn to_value(shard: &Shard) -> Value {
+    let mut fields = vec![
+        (Value::from("index"), Value::from(shard.index)),
+        (Value::from("total"), Value::from(shard.total)),
+        (Value::from("threshold"), Value::from(shard.threshold)),
+        (Value::from("version"), Value::from(shard.version)),
+        (Value::from("payload"), Value::Bytes(shard.payload.clone())),
+        (Value::from("checksum"), Value::from(shard.checksum)),
+    ];
+    if let Some(mac) = &shard.mac {
+        fields.push((Value::from("mac"), Value::Bytes(mac.to_vec())));
+    }
+    if let Some(clock) = &shard.maturity_clock {
+        fields.push((Value::from("release_at"), Value::from(clock.release_timestamp())));
+    }
+
+    Value::Map(fields)
+}
+
+pub(crate) fn from_value(value: Value) -> Result<Shard, TimelockError> {
+    let Value::Map(fields) = value else {
+        return Err(TimelockError::InvalidShardFormat("CBOR shard is not a map".to_string()));
+    };
+
+    let field = |name: &str| fields.iter()
+        .find(|(key, _)| key.as_text() == Some(name))
+        .map(|(_, value)| value);
+    let byte_field = |name: &str| -> Result<u8, TimelockError> {
+        field(name)
+            .and_then(Value::as_integer)
+            .and_then(|i: Integer| u8::try_from(i).ok())
+            .ok_or_else(|| TimelockError::InvalidShardFormat(format!(
+                "CBOR shard field `{}` is missing or not a byte", name)))
+    };
+
+    let payload = field("payload")
+        .and_then(Value::as_bytes)
+        .ok_or_else(|| TimelockError::InvalidShardFormat(
+            "CBOR shard field `payload` is missing or not a byte string".to_string()))?
+        .clone();
+    let checksum = field("checksum")
+        .and_then(Value::as_integer)
+        .and_then(|i: Integer| u32::try_from(i).ok())
+        .ok_or_else(|| TimelockError::InvalidShardFormat(
+            "CBOR shard field `checksum` is missing or not a u32".to_string()))?;
+    let mac = match field("mac") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(value.as_bytes()
+            .and_then(|mac| <[u8; MAC_LEN]>::try_from(mac.as_slice()).ok())
+            .ok_or_else(|| TimelockError::InvalidShardFormat(format!(
+                "CBOR shard field `mac` must be a {}-byte string", MAC_LEN)))?),
+    };
+
+    let maturity_clock = match field("release_at") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(value.as_integer()
+            .and_then(|i: Integer| u64::try_from(i).ok())
+            .map(MaturityClock::new)
+            .ok_or_else(|| TimelockError::InvalidShardFormat(
+                "CBOR shard field `release_at` must be a u64".to_string()))?),
+    };
+
+    let shard = Shard {
+        index: byte_field("index")?,
+        total: byte_field("total")?,
+        threshold: byte_field("threshold")?,
+        version: byte_field("version")?,
+        payload,
+        checksum,
+        mac,
+        maturity_clock,
+    };
+    shard.validate()?;
+    Ok(shard)
+}
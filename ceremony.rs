@@ -0,0 +1,76 @@
+// Commit-then-reveal shard distribution for key ceremonies
+//
+// Every custodian's commitment is published before the ceremony; each shard
+// revealed in person is then checked against its prior commitment, so nobody can
+// substitute a shard after seeing the others. Shard payloads are high-entropy,
+// so the bare hash does not reveal anything about the shard before the reveal.
+
+use crate::error::TimelockError;
+use crate::secret_data::SecretData;
+use crate::shard::Shard;
+use sha2::{Digest, Sha256};
+use std::hash::{Hash, Hasher};
+use subtle::{Choice, ConstantTimeEq};
+
+pub const SHARD_COMMITMENT_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct ShardCommitment(
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub [u8; SHARD_COMMITMENT_LEN],
+);
+
+impl SecretData for ShardCommitment {}
+
+impl ConstantTimeEq for ShardCommitment {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_slice().ct_eq(other.0.as_slice())
+    }
+}
+
+// Constant-time, so a forged shard cannot be tuned byte by byte against a commitment
+impl PartialEq for ShardCommitment {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for ShardCommitment {}
+
+impl Hash for ShardCommitment {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl ShardCommitment {
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, TimelockError> {
+        let bytes = hex::decode(s)?;
+        let commitment = <[u8; SHARD_COMMITMENT_LEN]>::try_from(bytes.as_slice())
+            .map_err(|_| TimelockError::InvalidShardFormat(format!(
+                "shard commitment must be {} bytes, found {}", SHARD_COMMITMENT_LEN, bytes.len())))?;
+        Ok(ShardCommitment(commitment))
+    }
+}
+
+// SHA256(shard.to_bytes())
+pub fn commit_shard(shard: &Shard) -> ShardCommitment {
+    ShardCommitment(Sha256::digest(shard.to_bytes()).into())
+}
+
+// Whether a revealed shard is the one committed to before the ceremony
+pub fn verify_commitment(shard: &Shard, commitment: &ShardCommitment) -> bool {
+    verify_shard_commitment(shard, commitment)
+}
+
+// Whether a stored shard still matches the commitment published for it at the
+// ceremony. Needs no other shard, so custodians can run it routinely to catch
+// a corrupted copy.
+pub fn verify_shard_commitment(shard: &Shard, commitment: &ShardCommitment) -> bool {
+    commit_shard(shard).ct_eq(commitment).into()
+}
@@ -0,0 +1,122 @@
+// On-disk state for resuming `LCS35::unlock_resumable` across sessions
+//
+// Layout (all integers little-endian):
+//   magic "LCS35CK1", iterations (u64), entry count (u32), then per entry
+//   fingerprint (32 bytes), squarings done (u64), value length (u32), value bytes
+//
+// Each entry is keyed by a fingerprint of its puzzle's modulus and base, so a
+// checkpoint can only ever resume the puzzle it was written for.
+
+use crate::error::TimelockError;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"LCS35CK1";
+
+pub(crate) struct Checkpoint {
+    // Squarings per puzzle the checkpoint was written for
+    pub iterations: u64,
+    pub entries: Vec<CheckpointEntry>,
+}
+
+pub(crate) struct CheckpointEntry {
+    pub fingerprint: [u8; 32],
+    // Number of squarings already applied to `value`
+    pub squarings: u64,
+    pub value: BigUint,
+}
+
+impl Checkpoint {
+    pub fn new(iterations: u64) -> Self {
+        Checkpoint { iterations, entries: Vec::new() }
+    }
+
+    // Read the checkpoint at `path`, or `None` if no checkpoint has been written yet
+    pub fn load(path: &Path) -> Result<Option<Self>, TimelockError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Self::parse(&bytes)
+            .map(Some)
+            .ok_or_else(|| TimelockError::PuzzleError(format!("checkpoint {} is corrupted", path.display())))
+    }
+
+    // Write to a sibling file first so an interruption never leaves a torn checkpoint
+    pub fn save(&self, path: &Path) -> Result<(), TimelockError> {
+        let partial = path.with_extension("partial");
+        fs::write(&partial, self.to_bytes())?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    // Index of the entry for the puzzle with this fingerprint, adding a fresh one
+    // starting from `base` if the puzzle has not been started yet
+    pub fn entry_for(&mut self, fingerprint: [u8; 32], base: &BigUint) -> usize {
+        if let Some(index) = self.entries.iter().position(|entry| entry.fingerprint == fingerprint) {
+            return index;
+        }
+        self.entries.push(CheckpointEntry { fingerprint, squarings: 0, value: base.clone() });
+        self.entries.len() - 1
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.iterations.to_le_bytes());
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for entry in &self.entries {
+            let value = entry.value.to_bytes_le();
+            bytes.extend_from_slice(&entry.fingerprint);
+            bytes.extend_from_slice(&entry.squarings.to_le_bytes());
+            bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&value);
+        }
+        bytes
+    }
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let rest = bytes.strip_prefix(MAGIC)?;
+        let (iterations, rest) = read_u64(rest)?;
+        let (count, mut rest) = read_u32(rest)?;
+
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let fingerprint: [u8; 32] = rest.get(..32)?.try_into().ok()?;
+            let (squarings, tail) = read_u64(&rest[32..])?;
+            let (len, tail) = read_u32(tail)?;
+            let value = tail.get(..len as usize)?;
+            entries.push(CheckpointEntry { fingerprint, squarings, value: BigUint::from_bytes_le(value) });
+            rest = &tail[len as usize..];
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+        Some(Checkpoint { iterations, entries })
+    }
+}
+
+// Identify a puzzle by its modulus and base
+pub(crate) fn fingerprint(modulus: &BigUint, base: &BigUint) -> [u8; 32] {
+    let modulus = modulus.to_bytes_le();
+    let mut hasher = Sha256::new();
+    hasher.update((modulus.len() as u32).to_le_bytes());
+    hasher.update(&modulus);
+    hasher.update(base.to_bytes_le());
+    hasher.finalize().into()
+}
+
+fn read_u64(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let value = u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?);
+    Some((value, &bytes[8..]))
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let value = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+    Some((value, &bytes[4..]))
+}
@@ -0,0 +1,35 @@
+// Dealer-side storage for the polynomial coefficients behind a shard set
+//
+// Whoever holds the coefficients can issue new shards with
+// `TimelockKeySharding::add_shard`, and their constant terms are the key itself,
+// so they are only ever kept encrypted under the dealer's storage key.
+
+use crate::encrypted_shard::{open_bytes, seal_bytes};
+use crate::error::TimelockError;
+use zeroize::Zeroizing;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SealedCoefficients {
+    // Random 12-byte nonce followed by the AES-256-GCM ciphertext and tag of the coefficients
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub ciphertext: Vec<u8>,
+}
+
+impl SealedCoefficients {
+    pub fn seal(coefficients: &[u8], key: &[u8; 32]) -> Self {
+        SealedCoefficients { ciphertext: seal_bytes(coefficients, key) }
+    }
+
+    pub fn open(&self, key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>, TimelockError> {
+        open_bytes(&self.ciphertext, key)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.ciphertext)
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, TimelockError> {
+        Ok(SealedCoefficients { ciphertext: hex::decode(s)? })
+    }
+}
@@ -0,0 +1,178 @@
+// TOML configuration files for `TimelockKeySharding`
+//
+//   [timelock]
+//   difficulty = 35
+//   threshold = 3
+//   hash = "sha256"
+//   entropy_min = 7.2
+//   entropy_measure = "shannon"
+//   borel_tolerance = 0.01
+//   borel_window_bits = 8
+//   min_key_bytes = 16
+//   max_key_bytes = 1024
+//
+// Every key but `threshold` and `difficulty` is optional and falls back to the
+// library default. In place of `difficulty`, a config may set
+//
+//   calibrate_difficulty = true
+//   target_years = 35
+//
+// to take the smallest difficulty lasting `target_years` at the rate saved by
+// `schrödinger bench` in `~/.schrödinger/benchmark.json`. Loaded values go
+// through `TimelockKeyShardingBuilder`, so a config file cannot produce a
+// configuration the builder would reject.
+
+use crate::benchmark::{default_benchmark_path, BenchmarkResult};
+use crate::difficulty::Difficulty;
+use crate::entropy::EntropyMeasure;
+use crate::error::TimelockError;
+use crate::hash_algorithm::HashAlgorithm;
+use crate::{TimelockKeySharding, TimelockKeyShardingBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFile {
+    timelock: TimelockSection,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimelockSection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    difficulty: Option<u32>,
+    threshold: usize,
+    #[serde(default, skip_serializing_if = "is_false")]
+    calibrate_difficulty: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target_years: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entropy_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entropy_measure: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    borel_tolerance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    borel_window_bits: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_key_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_key_bytes: Option<usize>,
+}
+
+pub(crate) fn load(path: &Path) -> Result<TimelockKeySharding, TimelockError> {
+    let text = fs::read_to_string(path)?;
+    let config: ConfigFile = toml::from_str(&text)
+        .map_err(|e| TimelockError::InvalidConfiguration(format!("{}: {}", path.display(), e)))?;
+    let section = config.timelock;
+
+    let mut builder = TimelockKeyShardingBuilder::default()
+        .difficulty(difficulty(&section, path)?)
+        .threshold(section.threshold);
+    if let Some(hash) = &section.hash {
+        builder = builder.hash_algorithm(parse_hash(hash)?);
+    }
+    if let Some(entropy_min) = section.entropy_min {
+        builder = builder.entropy_threshold(entropy_min);
+    }
+    if let Some(entropy_measure) = &section.entropy_measure {
+        builder = builder.entropy_measure(parse_entropy_measure(entropy_measure)?);
+    }
+    if let Some(borel_tolerance) = section.borel_tolerance {
+        builder = builder.borel_tolerance(borel_tolerance);
+    }
+    if let Some(borel_window_bits) = section.borel_window_bits {
+        builder = builder.borel_window_bits(borel_window_bits);
+    }
+    if let Some(min_key_bytes) = section.min_key_bytes {
+        builder = builder.min_key_bytes(min_key_bytes);
+    }
+    if let Some(max_key_bytes) = section.max_key_bytes {
+        builder = builder.max_key_bytes(max_key_bytes);
+    }
+    builder.build()
+}
+
+pub(crate) fn save(sharding: &TimelockKeySharding, path: &Path) -> Result<(), TimelockError> {
+    let config = ConfigFile {
+        timelock: TimelockSection {
+            difficulty: Some(sharding.difficulty),
+            threshold: sharding.threshold,
+            calibrate_difficulty: false,
+            target_years: None,
+            hash: Some(hash_name(sharding.hash_algorithm).to_string()),
+            entropy_min: Some(sharding.entropy_threshold),
+            entropy_measure: Some(entropy_measure_name(sharding.entropy_measure).to_string()),
+            borel_tolerance: Some(sharding.borel_tolerance),
+            borel_window_bits: Some(sharding.borel_window_bits),
+            min_key_bytes: Some(sharding.min_key_bytes),
+            max_key_bytes: Some(sharding.max_key_bytes),
+        },
+    };
+    let text = toml::to_string(&config)
+        .map_err(|e| TimelockError::InvalidConfiguration(e.to_string()))?;
+    fs::write(path, text)?;
+    Ok(())
+}
+
+// The configured difficulty, or with `calibrate_difficulty` the one computed
+// from the saved benchmark
+fn difficulty(section: &TimelockSection, path: &Path) -> Result<Difficulty, TimelockError> {
+    let invalid = |message: &str| TimelockError::InvalidConfiguration(format!("{}: {}", path.display(), message));
+    match (section.difficulty, section.calibrate_difficulty) {
+        (Some(difficulty), false) => Ok(Difficulty(difficulty)),
+        (None, false) => Err(invalid("set either difficulty or calibrate_difficulty")),
+        (Some(_), true) => Err(invalid("difficulty and calibrate_difficulty cannot both be set")),
+        (None, true) => {
+            let target_years = section.target_years
+                .filter(|years| years.is_finite() && *years > 0.0)
+                .ok_or_else(|| invalid("calibrate_difficulty needs a positive target_years"))?;
+            let benchmark_path = default_benchmark_path()
+                .ok_or_else(|| invalid("calibrate_difficulty needs a home directory to find the benchmark in"))?;
+            let benchmark = BenchmarkResult::load(&benchmark_path).map_err(|e| invalid(&format!(
+                "cannot read benchmark {} (run `schrödinger bench` first): {}", benchmark_path.display(), e)))?;
+            Ok(Difficulty::from_years(target_years, benchmark.squarings_per_second))
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn parse_hash(name: &str) -> Result<HashAlgorithm, TimelockError> {
+    match name.to_ascii_lowercase().as_str() {
+        "sha256" => Ok(HashAlgorithm::Sha256),
+        "blake3" => Ok(HashAlgorithm::Blake3),
+        _ => Err(TimelockError::InvalidConfiguration(format!(
+            "unknown hash algorithm {:?}, expected \"sha256\" or \"blake3\"", name))),
+    }
+}
+
+fn hash_name(hash: HashAlgorithm) -> &'static str {
+    match hash {
+        HashAlgorithm::Sha256 => "sha256",
+        HashAlgorithm::Blake3 => "blake3",
+    }
+}
+
+fn parse_entropy_measure(name: &str) -> Result<EntropyMeasure, TimelockError> {
+    match name.to_ascii_lowercase().as_str() {
+        "shannon" => Ok(EntropyMeasure::Shannon),
+        "min" => Ok(EntropyMeasure::Min),
+        "collision" => Ok(EntropyMeasure::Collision),
+        _ => Err(TimelockError::InvalidConfiguration(format!(
+            "unknown entropy measure {:?}, expected \"shannon\", \"min\" or \"collision\"", name))),
+    }
+}
+
+fn entropy_measure_name(measure: EntropyMeasure) -> &'static str {
+    match measure {
+        EntropyMeasure::Shannon => "shannon",
+        EntropyMeasure::Min => "min",
+        EntropyMeasure::Collision => "collision",
+    }
+}
@@ -0,0 +1,57 @@
+// Compute cost of reconstructing a key, for choosing a difficulty
+//
+// Each puzzle is a chain of sequential squarings, so its cost is one core busy for
+// the whole solve: CPU-hours equal wall-clock hours and extra cores do not help.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Squaring rate last measured by `LCS35::calibrate`, zero until then
+static LOCAL_SQUARINGS_PER_SECOND: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReconstructionCost {
+    // Sequential squarings in one shard's puzzle, 2^difficulty
+    pub total_squarings: u128,
+    pub estimated_seconds: f64,
+    pub estimated_cpu_hours: f64,
+}
+
+// Cost of solving one puzzle of `difficulty` at `squarings_per_second`
+pub fn estimate_reconstruction_cost(difficulty: u32, squarings_per_second: u64) -> ReconstructionCost {
+    estimate_reconstruction_cost_with_speedup(difficulty, squarings_per_second, 1.0)
+}
+
+// As `estimate_reconstruction_cost`, for hardware `hardware_speedup_factor` times
+// faster than the measured rate, e.g. 2^(years / 2) for a Moore's-law projection.
+// A zero rate or non-positive factor gives an infinite estimate.
+pub fn estimate_reconstruction_cost_with_speedup(difficulty: u32, squarings_per_second: u64, hardware_speedup_factor: f64)
+    -> ReconstructionCost
+{
+    let total_squarings = 1u128.checked_shl(difficulty).unwrap_or(u128::MAX);
+    let rate = squarings_per_second as f64 * hardware_speedup_factor;
+    let estimated_seconds = if rate > 0.0 { total_squarings as f64 / rate } else { f64::INFINITY };
+
+    ReconstructionCost {
+        total_squarings,
+        estimated_seconds,
+        estimated_cpu_hours: estimated_seconds / 3600.0,
+    }
+}
+
+// Squaring rate of this machine as measured by the last `LCS35::calibrate`
+pub fn local_squarings_per_second() -> Option<u64> {
+    match LOCAL_SQUARINGS_PER_SECOND.load(Ordering::Relaxed) {
+        0 => None,
+        rate => Some(rate),
+    }
+}
+
+// `estimate_reconstruction_cost` at the local benchmark rate, once `LCS35::calibrate` has run
+pub fn estimate_local_reconstruction_cost(difficulty: u32) -> Option<ReconstructionCost> {
+    local_squarings_per_second().map(|rate| estimate_reconstruction_cost(difficulty, rate))
+}
+
+pub(crate) fn record_local_benchmark(squarings_per_second: u64) {
+    LOCAL_SQUARINGS_PER_SECOND.store(squarings_per_second, Ordering::Relaxed);
+}
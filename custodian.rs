@@ -0,0 +1,60 @@
+// Delivering shards to named custodians
+//
+// Custodians are usually known by the name their public key is filed under
+// rather than by a shard number, so a dealer renumbers the shards in delivery
+// order. Only `Shard::index` changes: the x-coordinate at the front of the
+// payload is what reconstruction uses, and it stays as dealt.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct CustodianId(pub String);
+
+impl fmt::Display for CustodianId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for CustodianId {
+    fn from(id: &str) -> Self {
+        CustodianId(id.to_string())
+    }
+}
+
+impl From<String> for CustodianId {
+    fn from(id: String) -> Self {
+        CustodianId(id)
+    }
+}
+
+// Pair shards with custodians following `mapping`'s (shard index, custodian)
+// entries, renumbering them 1, 2, ... in that order. Shards the mapping leaves
+// out are dropped.
+//
+// A shard's MAC covers its index, so renumbering invalidates it: with the
+// dealer's `mac_key` (see `Shard::derive_mac_key`) each shard is re-tagged,
+// and without one its MAC is removed rather than left stale.
+//
+// Returns `Result` because `mapping` is checked against the shards as it is
+// applied: an index with no shard, or one listed twice, is an
+// `InvalidConfiguration` error and no shards are returned.
+pub fn remap_shards(mut shards: Vec<Shard>, mapping: &[(u8, CustodianId)], mac_key: Option<&[u8]>)
+    -> Result<Vec<(CustodianId, Shard)>, TimelockError>
+{
+    let mut remapped = Vec::with_capacity(mapping.len());
+    for (position, (index, custodian)) in mapping.iter().enumerate() {
+        let found = shards.iter()
+            .position(|shard| shard.index == *index)
+            .ok_or_else(|| TimelockError::InvalidConfiguration(format!(
+                "no shard with index {} is left to give to {}", index, custodian)))?;
+        let mut shard = shards.swap_remove(found);
+        shard.index = (position + 1) as u8;
+        shard.mac = mac_key.map(|mac_key| shard.compute_mac(mac_key));
+        remapped.push((custodian.clone(), shard));
+    }
+    Ok(remapped)
+}
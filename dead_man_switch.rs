@@ -0,0 +1,87 @@
+// Release of shards to their custodians when the key owner stops checking in
+
+use crate::cancellation::CancellationToken;
+use crate::shard::Shard;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Shortest pause between checks, so tiny intervals do not spin a core
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+// Hands `shards` to `on_trigger` once more than `interval` has passed since the
+// last `checkin`. Share it in an `Arc`: one clone runs the watcher from `spawn`,
+// the owner checks in through another.
+pub struct DeadManSwitch {
+    // Milliseconds since the Unix epoch
+    last_checkin: Arc<AtomicU64>,
+    interval: Duration,
+    shards: Vec<Shard>,
+    on_trigger: Box<dyn Fn(Vec<Shard>) + Send + Sync>,
+    disarmed: CancellationToken,
+}
+
+impl DeadManSwitch {
+    // Armed with the owner counted as having just checked in
+    pub fn new<F>(shards: Vec<Shard>, interval: Duration, on_trigger: F) -> Self
+    where
+        F: Fn(Vec<Shard>) + Send + Sync + 'static,
+    {
+        DeadManSwitch {
+            last_checkin: Arc::new(AtomicU64::new(now_millis())),
+            interval,
+            shards,
+            on_trigger: Box::new(on_trigger),
+            disarmed: CancellationToken::new(),
+        }
+    }
+
+    pub fn checkin(&self) {
+        self.last_checkin.store(now_millis(), Ordering::Relaxed);
+    }
+
+    // Stop the watcher without releasing anything; it exits at its next poll
+    pub fn disarm(&self) {
+        self.disarmed.cancel();
+    }
+
+    // Whether more than `interval` has passed since the last check-in
+    pub fn is_overdue(&self) -> bool {
+        let elapsed = now_millis().saturating_sub(self.last_checkin.load(Ordering::Relaxed));
+        u128::from(elapsed) > self.interval.as_millis()
+    }
+
+    // Watch for a missed check-in on a background thread, polling every tenth of
+    // `interval`. The thread calls `on_trigger` at most once, then exits; it
+    // also exits once the switch is disarmed.
+    pub fn spawn(self: Arc<Self>) -> thread::JoinHandle<()> {
+        let poll_interval = (self.interval / 10).max(MIN_POLL_INTERVAL);
+        thread::spawn(move || {
+            while !self.disarmed.is_cancelled() {
+                if self.is_overdue() {
+                    (self.on_trigger)(self.shards.clone());
+                    return;
+                }
+                thread::sleep(poll_interval);
+            }
+        })
+    }
+}
+
+// Never prints the shards
+impl fmt::Debug for DeadManSwitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadManSwitch")
+            .field("last_checkin", &self.last_checkin.load(Ordering::Relaxed))
+            .field("interval", &self.interval)
+            .field("shards", &self.shards.len())
+            .field("disarmed", &self.disarmed.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
@@ -0,0 +1,33 @@
+// Decoy shards for custodians who need to plausibly deny holding a real one
+//
+// A decoy has the binary format, puzzle layout and MAC of a real `LCS35` shard,
+// but every value in it is random, so its entropy metrics are those of a real
+// shard of the same length and it passes `check_shard_entropy` whenever real
+// shards do. Only combining it with real shards of the same set (which share one
+// modulus) tells it apart.
+
+use crate::shard::{Shard, MAC_LEN};
+use crate::silurian_puzzle;
+use rand::RngCore;
+
+// Key length `generate_decoy` pretends to lock, matching a 256-bit key
+const DECOY_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecoyShardGenerator;
+
+impl DecoyShardGenerator {
+    pub fn generate_decoy(index: u8, total: u8, threshold: u8, rng: &mut impl RngCore) -> Shard {
+        Self::generate_decoy_for_key_len(index, total, threshold, DECOY_KEY_LEN, rng)
+    }
+
+    // Decoy the same size as real shards of a `key_len`-byte key, since a shorter
+    // or longer payload than the rest of the set would give it away
+    pub fn generate_decoy_for_key_len(index: u8, total: u8, threshold: u8, key_len: usize, rng: &mut impl RngCore) -> Shard {
+        let mut shard = Shard::new(index, total, threshold, silurian_puzzle::decoy_payload(index, key_len, rng));
+        let mut mac = [0u8; MAC_LEN];
+        rng.fill_bytes(&mut mac);
+        shard.mac = Some(mac);
+        shard
+    }
+}
@@ -0,0 +1,47 @@
+// Timelock difficulty: each shard's puzzle takes 2^difficulty sequential squarings
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 86400.0;
+
+// Largest difficulty whose squaring count still fits in a u64
+pub const MAX_DIFFICULTY: u32 = 63;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Difficulty(pub u32);
+
+impl Difficulty {
+    // Smallest difficulty keeping one puzzle locked for `years` on hardware doing
+    // `squarings_per_sec` sequential squarings, clamped to 1-63
+    pub fn from_years(years: f64, squarings_per_sec: u64) -> Self {
+        let squarings = years * SECONDS_PER_YEAR * squarings_per_sec as f64;
+        Difficulty((squarings.log2().ceil().max(1.0) as u32).min(MAX_DIFFICULTY))
+    }
+
+    // Years one puzzle takes to solve at `squarings_per_sec`
+    pub fn to_years(self, squarings_per_sec: u64) -> f64 {
+        2f64.powi(self.0 as i32) / squarings_per_sec as f64 / SECONDS_PER_YEAR
+    }
+
+    // Sequential squarings one puzzle takes, 2^difficulty. Above `MAX_DIFFICULTY`
+    // that overflows a u64, so the count saturates at u64::MAX squarings, which
+    // no machine finishes anyway.
+    pub fn iterations(self) -> u64 {
+        2u64.checked_pow(self.0).unwrap_or(u64::MAX)
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for Difficulty {
+    fn from(difficulty: u32) -> Self {
+        Difficulty(difficulty)
+    }
+}
+
+impl From<Difficulty> for u32 {
+    fn from(difficulty: Difficulty) -> Self {
+        difficulty.0
+    }
+}
@@ -0,0 +1,32 @@
+// Shards as DNS TXT record values, for custodians publishing through DNS
+//
+// `Shard::to_bytes` is base64-encoded and cut into character-strings of at most
+// 255 bytes. The strings of one TXT record come back from a resolver in order
+// and are read as their concatenation.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+// Longest character-string a TXT record can hold
+pub const TXT_STRING_LEN: usize = 255;
+
+pub fn shard_to_dns_txt(shard: &Shard) -> Vec<String> {
+    let encoded = STANDARD.encode(shard.to_bytes());
+    encoded.as_bytes()
+        .chunks(TXT_STRING_LEN)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ASCII").to_string())
+        .collect()
+}
+
+// Inverse of `shard_to_dns_txt`. Strings may keep the double quotes they are
+// written with in zone files.
+pub fn shard_from_dns_txt(records: &[&str]) -> Result<Shard, TimelockError> {
+    let encoded: String = records.iter()
+        .map(|record| record.trim().trim_matches('"'))
+        .collect();
+    let bytes = STANDARD.decode(encoded)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("bad base64 in TXT record: {}", e)))?;
+    Shard::from_bytes(&bytes)
+}
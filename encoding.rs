@@ -0,0 +1,3 @@
+// Text encodings of shards beyond hex, PEM and mnemonic words
+
+pub mod emoji;
@@ -0,0 +1,50 @@
+// Shards as a string of emoji, one per byte of `Shard::to_bytes`
+//
+// Every symbol is a single code point drawn with emoji presentation by default,
+// so none needs a variation selector, skin tone or zero-width joiner and the
+// string survives any UTF-8 handling that keeps characters intact. The
+// alphabet is sorted by code point: food U+1F345-U+1F36F, animals
+// U+1F400-U+1F43E, faces and gestures U+1F600-U+1F64F and transport
+// U+1F680-U+1F6C5.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+
+// Byte `b` is written as `ALPHABET[b]`
+pub const ALPHABET: [char; 256] = [
+    '🍅', '🍆', '🍇', '🍈', '🍉', '🍊', '🍋', '🍌', '🍍', '🍎', '🍏', '🍐', '🍑', '🍒', '🍓', '🍔',
+    '🍕', '🍖', '🍗', '🍘', '🍙', '🍚', '🍛', '🍜', '🍝', '🍞', '🍟', '🍠', '🍡', '🍢', '🍣', '🍤',
+    '🍥', '🍦', '🍧', '🍨', '🍩', '🍪', '🍫', '🍬', '🍭', '🍮', '🍯', '🐀', '🐁', '🐂', '🐃', '🐄',
+    '🐅', '🐆', '🐇', '🐈', '🐉', '🐊', '🐋', '🐌', '🐍', '🐎', '🐏', '🐐', '🐑', '🐒', '🐓', '🐔',
+    '🐕', '🐖', '🐗', '🐘', '🐙', '🐚', '🐛', '🐜', '🐝', '🐞', '🐟', '🐠', '🐡', '🐢', '🐣', '🐤',
+    '🐥', '🐦', '🐧', '🐨', '🐩', '🐪', '🐫', '🐬', '🐭', '🐮', '🐯', '🐰', '🐱', '🐲', '🐳', '🐴',
+    '🐵', '🐶', '🐷', '🐸', '🐹', '🐺', '🐻', '🐼', '🐽', '🐾', '😀', '😁', '😂', '😃', '😄', '😅',
+    '😆', '😇', '😈', '😉', '😊', '😋', '😌', '😍', '😎', '😏', '😐', '😑', '😒', '😓', '😔', '😕',
+    '😖', '😗', '😘', '😙', '😚', '😛', '😜', '😝', '😞', '😟', '😠', '😡', '😢', '😣', '😤', '😥',
+    '😦', '😧', '😨', '😩', '😪', '😫', '😬', '😭', '😮', '😯', '😰', '😱', '😲', '😳', '😴', '😵',
+    '😶', '😷', '😸', '😹', '😺', '😻', '😼', '😽', '😾', '😿', '🙀', '🙁', '🙂', '🙃', '🙄', '🙅',
+    '🙆', '🙇', '🙈', '🙉', '🙊', '🙋', '🙌', '🙍', '🙎', '🙏', '🚀', '🚁', '🚂', '🚃', '🚄', '🚅',
+    '🚆', '🚇', '🚈', '🚉', '🚊', '🚋', '🚌', '🚍', '🚎', '🚏', '🚐', '🚑', '🚒', '🚓', '🚔', '🚕',
+    '🚖', '🚗', '🚘', '🚙', '🚚', '🚛', '🚜', '🚝', '🚞', '🚟', '🚠', '🚡', '🚢', '🚣', '🚤', '🚥',
+    '🚦', '🚧', '🚨', '🚩', '🚪', '🚫', '🚬', '🚭', '🚮', '🚯', '🚰', '🚱', '🚲', '🚳', '🚴', '🚵',
+    '🚶', '🚷', '🚸', '🚹', '🚺', '🚻', '🚼', '🚽', '🚾', '🚿', '🛀', '🛁', '🛂', '🛃', '🛄', '🛅',
+];
+
+// Variation selector some platforms append to emoji when they are pasted
+const EMOJI_PRESENTATION_SELECTOR: char = '\u{fe0f}';
+
+pub fn encode(shard: &Shard) -> String {
+    shard.to_bytes().iter().map(|&byte| ALPHABET[byte as usize]).collect()
+}
+
+// Whitespace and emoji presentation selectors are skipped
+pub fn decode(s: &str) -> Result<Shard, TimelockError> {
+    let bytes = s.chars()
+        .filter(|&c| !c.is_whitespace() && c != EMOJI_PRESENTATION_SELECTOR)
+        .map(|c| ALPHABET.binary_search(&c)
+            .map(|byte| byte as u8)
+            .map_err(|_| TimelockError::InvalidShardFormat(format!(
+                "U+{:04X} is not in the shard emoji alphabet", c as u32))))
+        .collect::<Result<Vec<u8>, _>>()?;
+    Shard::from_bytes(&bytes)
+}
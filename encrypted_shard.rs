@@ -0,0 +1,74 @@
+// Shards encrypted under a transport key for delivery to their custodians
+
+use crate::error::TimelockError;
+use crate::kdf::KeyDerivationRatchet;
+use crate::shard::Shard;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncryptedShard {
+    // Random 12-byte nonce followed by the AES-256-GCM ciphertext and tag of `Shard::to_bytes`
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedShard {
+    pub(crate) fn seal(shard: &Shard, key: &[u8; 32]) -> Self {
+        let plaintext = Zeroizing::new(shard.to_bytes());
+        EncryptedShard { ciphertext: seal_bytes(&plaintext, key) }
+    }
+
+    // Encrypt under the key `ratchet` derives for the shard's index; its custodian
+    // decrypts with `ratchet.derive_shard_key(shard.index)`
+    pub fn with_ratchet(shard: &Shard, ratchet: &KeyDerivationRatchet) -> Self {
+        let key = Zeroizing::new(ratchet.derive_shard_key(shard.index));
+        Self::seal(shard, &key)
+    }
+
+    pub fn decrypt(&self, key: &[u8; 32]) -> Result<Shard, TimelockError> {
+        Shard::from_bytes(&open_bytes(&self.ciphertext, key)?)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.ciphertext)
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, TimelockError> {
+        Ok(EncryptedShard { ciphertext: hex::decode(s)? })
+    }
+}
+
+// AES-256-GCM under a random nonce, returned as nonce || ciphertext || tag
+pub(crate) fn seal_bytes(plaintext: &[u8], key: &[u8; 32]) -> Vec<u8> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let sealed = Aes256Gcm::new(key.into())
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+
+    let mut ciphertext = Vec::with_capacity(NONCE_LEN + sealed.len());
+    ciphertext.extend_from_slice(&nonce);
+    ciphertext.extend_from_slice(&sealed);
+    ciphertext
+}
+
+// Inverse of `seal_bytes`
+pub(crate) fn open_bytes(ciphertext: &[u8], key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>, TimelockError> {
+    if ciphertext.len() < NONCE_LEN {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "ciphertext is shorter than its {}-byte nonce", NONCE_LEN)));
+    }
+
+    let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map(Zeroizing::new)
+        .map_err(|_| TimelockError::DecryptionFailed)
+}
@@ -0,0 +1,230 @@
+// Statistical checks applied to shard payloads by `TimelockKeySharding::check_shard_entropy`
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+// Which entropy estimate the shard entropy threshold is compared against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EntropyMeasure {
+    #[default]
+    Shannon,
+    Min,
+    Collision,
+}
+
+impl EntropyMeasure {
+    pub fn measure(self, data: &[u8]) -> f64 {
+        match self {
+            EntropyMeasure::Shannon => calculate_entropy(data),
+            EntropyMeasure::Min => calculate_min_entropy(data),
+            EntropyMeasure::Collision => calculate_collision_entropy(data),
+        }
+    }
+}
+
+// Shannon entropy of the byte distribution, in bits per byte
+pub fn calculate_entropy(data: &[u8]) -> f64 {
+    byte_probabilities(data)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+// Min-entropy -log2(max p), in bits per byte; the guessing resistance of the
+// most likely byte value
+pub fn calculate_min_entropy(data: &[u8]) -> f64 {
+    let max_p = byte_probabilities(data).fold(0.0, f64::max);
+    if max_p == 0.0 {
+        return 0.0;
+    }
+    -max_p.log2()
+}
+
+// Rényi entropy of order 2, -log2(sum p^2), in bits per byte
+pub fn calculate_collision_entropy(data: &[u8]) -> f64 {
+    let collision_probability: f64 = byte_probabilities(data).map(|p| p * p).sum();
+    if collision_probability == 0.0 {
+        return 0.0;
+    }
+    -collision_probability.log2()
+}
+
+// Probability of every byte value that occurs in the data
+fn byte_probabilities(data: &[u8]) -> impl Iterator<Item = f64> {
+    let mut counts = [0u32; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+
+    let len = data.len() as f64;
+    counts.into_iter()
+        .filter(|&count| count > 0)
+        .map(move |count| count as f64 / len)
+}
+
+// 99th percentile of the chi-squared distribution with 255 degrees of freedom
+pub const CHI_SQUARED_CRITICAL_255: f64 = 310.457;
+
+// Pearson's chi-squared statistic of the byte counts against a uniform
+// distribution over all 256 values, and whether it stays below the 99th
+// percentile critical value. Catches skewed distributions, such as printable
+// ASCII only, that still look fine to Shannon entropy. Empty data fails.
+pub fn chi_squared_uniformity(data: &[u8]) -> (f64, bool) {
+    if data.is_empty() {
+        return (0.0, false);
+    }
+
+    let mut counts = [0u32; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+
+    let expected = data.len() as f64 / 256.0;
+    let statistic: f64 = counts.iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+    (statistic, statistic <= CHI_SQUARED_CRITICAL_255)
+}
+
+// Spectral peaks above this multiple of sqrt(n), the root-mean-square DFT
+// magnitude of n random +/-1 bits, fail `spectral_test`
+pub const SPECTRAL_PEAK_FACTOR: f64 = 3.0;
+
+// Outcome of `spectral_test`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectralTestResult {
+    pub passed: bool,
+    // Largest DFT magnitude over the non-constant frequencies
+    pub max_peak: f64,
+    pub threshold: f64,
+}
+
+// Discrete Fourier transform test: map the bits (most significant first) to
+// +/-1, transform them and check that no frequency up to n/2 stands out.
+// A peak means the bits repeat with some period, as the output of a short-period
+// RNG would. The constant term only measures bit balance and is skipped. Data
+// shorter than one byte fails.
+pub fn spectral_test(data: &[u8]) -> SpectralTestResult {
+    let n = data.len() * 8;
+    if n == 0 {
+        return SpectralTestResult { passed: false, max_peak: 0.0, threshold: 0.0 };
+    }
+
+    let mut spectrum: Vec<Complex<f64>> = bits(data)
+        .map(|bit| Complex::new(if bit { 1.0 } else { -1.0 }, 0.0))
+        .collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut spectrum);
+
+    let max_peak = spectrum[1..=n / 2].iter().map(|value| value.norm()).fold(0.0, f64::max);
+    let threshold = SPECTRAL_PEAK_FACTOR * (n as f64).sqrt();
+    SpectralTestResult { passed: max_peak <= threshold, max_peak, threshold }
+}
+
+// Length of the shortest linear feedback shift register that generates `bits`,
+// found with the Berlekamp-Massey algorithm over GF(2)
+pub fn linear_complexity(bits: &[bool]) -> usize {
+    let n = bits.len();
+    // Connection polynomial, and its value before the last length change
+    let mut connection = vec![false; n + 1];
+    connection[0] = true;
+    let mut previous = connection.clone();
+    let mut length = 0;
+    // One past the position of the last length change
+    let mut last_change = 0;
+
+    for i in 0..n {
+        let mut discrepancy = bits[i];
+        for j in 1..=length {
+            discrepancy ^= connection[j] & bits[i - j];
+        }
+        if !discrepancy {
+            continue;
+        }
+
+        let before = connection.clone();
+        let shift = i + 1 - last_change;
+        for j in shift..=n {
+            connection[j] ^= previous[j - shift];
+        }
+        if 2 * length <= i {
+            length = i + 1 - length;
+            last_change = i + 1;
+            previous = before;
+        }
+    }
+    length
+}
+
+// Expand the data to bits (most significant first) and check that their linear
+// complexity is within `tolerance * n` of `expected_complexity`, which is about
+// n/2 for random bits. Empty data fails.
+pub fn linear_complexity_test(data: &[u8], expected_complexity: f64, tolerance: f64) -> bool {
+    let bits = to_bits(data);
+    !bits.is_empty()
+        && (linear_complexity(&bits) as f64 - expected_complexity).abs() <= tolerance * bits.len() as f64
+}
+
+// Fraction of zero bits in the data; 0.5 is perfectly balanced
+pub fn zero_bit_ratio(data: &[u8]) -> f64 {
+    let ones: u32 = data.iter().map(|byte| byte.count_ones()).sum();
+    let total = data.len() as f64 * 8.0;
+    (total - ones as f64) / total
+}
+
+// Slide a `window_bits`-wide window one bit at a time across the data (most
+// significant bit first) and check that every one of the 2^w patterns occurs
+// with a frequency within `tolerance` of 1/2^w. Data shorter than the window
+// fails, as does any `window_bits` outside 1..=16.
+pub fn check_borel_regularity_windowed(data: &[u8], window_bits: usize, tolerance: f64) -> bool {
+    let total_bits = data.len() * 8;
+    if !(1..=16).contains(&window_bits) || total_bits < window_bits {
+        return false;
+    }
+
+    let mask = (1u32 << window_bits) - 1;
+    let mut counts = vec![0u32; 1 << window_bits];
+    let mut window = 0u32;
+    for (position, bit) in bits(data).enumerate() {
+        window = ((window << 1) | bit as u32) & mask;
+        if position + 1 >= window_bits {
+            counts[window as usize] += 1;
+        }
+    }
+
+    let windows = (total_bits - window_bits + 1) as f64;
+    let expected = 1.0 / (1u32 << window_bits) as f64;
+    counts.iter().all(|&count| (count as f64 / windows - expected).abs() <= tolerance)
+}
+
+// Number of maximal runs of identical bits
+pub fn count_runs(data: &[u8]) -> usize {
+    let mut previous = None;
+    let mut runs = 0;
+    for bit in bits(data) {
+        if previous != Some(bit) {
+            runs += 1;
+            previous = Some(bit);
+        }
+    }
+    runs
+}
+
+// Runs per bit; a random sequence of n bits has about n/2 runs, so this is near 0.5
+pub fn runs_ratio(data: &[u8]) -> f64 {
+    count_runs(data) as f64 / (data.len() * 8) as f64
+}
+
+// Runs test: the run count must be within `tolerance * n` of the expected n/2
+pub fn check_runs(data: &[u8], tolerance: f64) -> bool {
+    !data.is_empty() && (runs_ratio(data) - 0.5).abs() <= tolerance
+}
+
+// Bits of the data, most significant bit of each byte first
+pub fn to_bits(data: &[u8]) -> Vec<bool> {
+    bits(data).collect()
+}
+
+fn bits(data: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    data.iter().flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+}
@@ -0,0 +1,117 @@
+// Error type shared by every fallible operation in the timelock key sharding system
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::string::FromUtf8Error;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum TimelockError {
+    // Fewer shards were supplied than the reconstruction threshold requires
+    InsufficientShards { provided: usize, required: usize },
+    // A hex-encoded shard could not be decoded
+    HexDecodeError(hex::FromHexError),
+    // The reconstructed key bytes are not a valid UTF-8 string
+    InvalidUtf8(FromUtf8Error),
+    // The shard at `index` is truncated, malformed or inconsistent with the others
+    CorruptedShard { index: usize },
+    // More than one supplied shard uses the x-coordinate `index`
+    DuplicateShard { index: u8 },
+    // The timelock puzzle itself could not be created or solved
+    PuzzleError(String),
+    // A sharding configuration failed validation
+    InvalidConfiguration(String),
+    // A serialized shard does not follow the shard binary format
+    InvalidShardFormat(String),
+    // Puzzle solving was stopped through a `CancellationToken`
+    Cancelled,
+    // Reading or writing a file failed
+    Io(io::Error),
+    // An encrypted shard failed authentication under the supplied key
+    DecryptionFailed,
+    // A storage backend failed to read or write shards
+    Storage(String),
+    // A shard signature does not verify under the supplied public key
+    InvalidSignature,
+    // Too many reconstructions failed recently; try again after `retry_after`
+    RateLimited { retry_after: Duration },
+    // A shard's maturity clock has not yet reached its release time (Unix seconds)
+    NotYetMature { release_at: u64 },
+    // The key to shard is shorter than the configured minimum
+    KeyTooShort { len: usize, min: usize },
+    // The key to shard is longer than the configured maximum
+    KeyTooLong { len: usize, max: usize },
+    // More shards were requested than one-byte x-coordinates can tell apart
+    TooManyShards { requested: usize, max: usize },
+}
+
+impl fmt::Display for TimelockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimelockError::InsufficientShards { provided, required } => {
+                write!(f, "Need at least {} shards, but only {} provided", required, provided)
+            }
+            TimelockError::HexDecodeError(e) => write!(f, "Failed to decode hex: {}", e),
+            TimelockError::InvalidUtf8(_) => write!(f, "Reconstructed key is not valid UTF-8"),
+            TimelockError::CorruptedShard { index } => write!(f, "Shard {} is corrupted", index),
+            TimelockError::DuplicateShard { index } => {
+                write!(f, "Shard with x-coordinate {} was provided more than once", index)
+            }
+            TimelockError::PuzzleError(msg) => write!(f, "Timelock puzzle error: {}", msg),
+            TimelockError::InvalidConfiguration(msg) => write!(f, "Invalid configuration: {}", msg),
+            TimelockError::InvalidShardFormat(msg) => write!(f, "Invalid shard format: {}", msg),
+            TimelockError::Cancelled => write!(f, "Operation was cancelled"),
+            TimelockError::Io(e) => write!(f, "I/O error: {}", e),
+            TimelockError::DecryptionFailed => {
+                write!(f, "Shard decryption failed: wrong key or tampered ciphertext")
+            }
+            TimelockError::Storage(msg) => write!(f, "Storage error: {}", msg),
+            TimelockError::InvalidSignature => write!(f, "Shard signature is invalid"),
+            TimelockError::RateLimited { retry_after } => {
+                write!(f, "Too many failed reconstructions, retry in {}s", retry_after.as_secs())
+            }
+            TimelockError::NotYetMature { release_at } => {
+                write!(f, "Shard is not released until Unix time {}", release_at)
+            }
+            TimelockError::KeyTooShort { len, min } => {
+                write!(f, "Key is {} bytes, but at least {} are required", len, min)
+            }
+            TimelockError::KeyTooLong { len, max } => {
+                write!(f, "Key is {} bytes, but at most {} are allowed", len, max)
+            }
+            TimelockError::TooManyShards { requested, max } => {
+                write!(f, "Requested {} shards, but at most {} are supported", requested, max)
+            }
+        }
+    }
+}
+
+impl Error for TimelockError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TimelockError::HexDecodeError(e) => Some(e),
+            TimelockError::InvalidUtf8(e) => Some(e),
+            TimelockError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<hex::FromHexError> for TimelockError {
+    fn from(e: hex::FromHexError) -> Self {
+        TimelockError::HexDecodeError(e)
+    }
+}
+
+impl From<FromUtf8Error> for TimelockError {
+    fn from(e: FromUtf8Error) -> Self {
+        TimelockError::InvalidUtf8(e)
+    }
+}
+
+impl From<io::Error> for TimelockError {
+    fn from(e: io::Error) -> Self {
+        TimelockError::Io(e)
+    }
+}
@@ -0,0 +1,209 @@
+// Walk through the main features of the library on a small demo key
+//
+//     cargo run --example demo
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{
+    verify_shard_set, Argon2Params, CancellationToken, HashAlgorithm, SealedCoefficients, Shard,
+    TimelockKeySharding, TimelockKeyShardingBuilder, XorSplit, LCS35,
+};
+
+fn main() {
+    println!("Project Schrödinger - Timelock Key Sharding Demo");
+    
+    // Create a key sharding system with:
+    // - difficulty level 10 (for demo - real system would use much higher)
+    // - threshold of 3 shards needed to reconstruct
+    let sharding = TimelockKeyShardingBuilder::default()
+        .difficulty(10)
+        .threshold(3)
+        .total_shards(5)
+        .build()
+        .expect("demo configuration is valid");
+    
+    // Generate a random key
+    let key = "supersecret_ai_model_encryption_key_2024";
+    println!("Original key: {}", key);
+    
+    // Shard the key into 5 pieces
+    let shards = sharding.shard_key(key, sharding.total_shards().unwrap_or(5))
+        .expect("demo key is within the key length limits");
+    println!("Generated {} shards:", shards.len());
+    
+    for (i, shard) in shards.iter().enumerate() {
+        println!("Shard {}: {:.20}...", i + 1, shard.to_hex());
+    }
+    
+    // Check entropy of shards
+    let entropy_report = sharding.check_shard_entropy(&shards);
+    println!("Shard entropy check: {}", if entropy_report.passed { "PASSED" } else { "FAILED" });
+    for metrics in &entropy_report.per_shard {
+        println!("  Shard {}: {:.2} bits/byte (min {:.2}, collision {:.2}), zero-bit ratio {:.3}, runs ratio {:.3}, chi-squared {:.1}, spectral peak {:.1}/{:.1}",
+                 metrics.index + 1, metrics.shannon_entropy, metrics.min_entropy, metrics.collision_entropy,
+                 metrics.borel_regularity_ratio, metrics.runs_ratio, metrics.chi_squared_stat,
+                 metrics.spectral.max_peak, metrics.spectral.threshold);
+    }
+    
+    // Pre-flight check of the shard set before any puzzle is solved
+    match verify_shard_set(&shards) {
+        Ok(info) => println!("Shard set: {}-of-{}, version {}, all valid: {}",
+                             info.threshold, info.total, info.version, info.all_valid),
+        Err(e) => println!("Shard set is inconsistent: {}", e),
+    }
+    
+    // Demonstrate reconstruction (with 3 shards)
+    let subset = shards.iter().take(3).cloned().collect::<Vec<_>>();
+    match sharding.reconstruct_key(&subset) {
+        Ok(reconstructed) => {
+            println!("Key reconstruction successful!");
+            println!("Reconstructed key: {}", reconstructed);
+            println!("Key matches: {}", reconstructed == key);
+        },
+        Err(e) => {
+            println!("Key reconstruction failed: {}", e);
+        }
+    }
+    
+    // Demonstrate progress reporting while the puzzles are solved
+    let puzzle = LCS35::for_unlock(sharding.difficulty()).with_progress_interval(1024);
+    let cancel = CancellationToken::new();
+    let progress = puzzle.unlock_with_progress(&subset, 3, |completed, total| {
+        println!("  Squarings completed: {}/{}", completed, total);
+    }, &cancel);
+    println!("Reconstruction with progress succeeded: {}", progress.is_ok());
+    
+    // Cancel from inside the callback, as a UI cancel button would from another thread
+    let cancel_after_first = cancel.clone();
+    match puzzle.unlock_with_progress(&subset, 3, move |_, _| cancel_after_first.cancel(), &cancel) {
+        Ok(_) => println!("WARNING: Cancelled reconstruction still completed!"),
+        Err(e) => println!("Expected failure after cancelling: {}", e),
+    }
+    
+    // Demonstrate Blake3 keystreams; the algorithm travels with each shard
+    let blake3_puzzle = LCS35::new_with_hash(sharding.difficulty(), HashAlgorithm::Blake3);
+    let blake3_shards = blake3_puzzle.shard(key.as_bytes(), 5, 3, &mut rand::thread_rng())
+        .expect("5 shards with threshold 3 is a valid split");
+    match LCS35::for_unlock(sharding.difficulty()).unlock(&blake3_shards[..3], 3) {
+        Ok(reconstructed) => println!("Blake3 reconstruction matches: {}", reconstructed == key.as_bytes()),
+        Err(e) => println!("Blake3 reconstruction failed: {}", e),
+    }
+    
+    // Demonstrate resumable solving with an on-disk checkpoint
+    let checkpoint_path = std::env::temp_dir().join("schrodinger-demo.checkpoint");
+    let resumable = LCS35::for_unlock(sharding.difficulty()).with_checkpoint_interval(256);
+    match resumable.unlock_resumable(&subset, 3, &checkpoint_path) {
+        Ok(reconstructed) => println!("Resumable reconstruction matches: {}, checkpoint removed: {}",
+                                      reconstructed == key.as_bytes(), !checkpoint_path.exists()),
+        Err(e) => println!("Resumable reconstruction failed: {}", e),
+    }
+    
+    // Demonstrate rotating to a new 2-of-4 custodian set
+    match sharding.rotate_shards(&subset, 4, 2, &mut rand::thread_rng()) {
+        Ok(rotated) => match LCS35::for_unlock(sharding.difficulty()).unlock(&rotated[2..], 2) {
+            Ok(reconstructed) => println!("Rotated shards reconstruct the key: {}", reconstructed == key.as_bytes()),
+            Err(e) => println!("Rotated shard reconstruction failed: {}", e),
+        },
+        Err(e) => println!("Shard rotation failed: {}", e),
+    }
+    
+    // Demonstrate issuing a shard to a new custodian from sealed coefficients
+    let mut storage_key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut storage_key);
+    let (dealt, coefficients) = sharding.shard_with_coefficients(key.as_bytes(), 5, &mut rand::thread_rng())
+        .expect("demo key is within the key length limits");
+    let sealed = SealedCoefficients::seal(&coefficients, &storage_key);
+    let added = sealed.open(&storage_key)
+        .and_then(|coefficients| sharding.add_shard(&dealt, 6, &coefficients));
+    match added {
+        Ok(new_shard) => {
+            let mixed = vec![dealt[0].clone(), dealt[4].clone(), new_shard];
+            match sharding.reconstruct_bytes(&mixed) {
+                Ok(reconstructed) => println!("Added shard reconstructs the key: {}", reconstructed == key.as_bytes()),
+                Err(e) => println!("Reconstruction with the added shard failed: {}", e),
+            }
+        }
+        Err(e) => println!("Adding a shard failed: {}", e),
+    }
+    
+    // Demonstrate swapping in n-of-n XOR splitting
+    let xor_sharding = TimelockKeySharding::new(sharding.difficulty(), 5).with_splitter(Box::new(XorSplit));
+    let xor_shards = xor_sharding.shard_bytes(key.as_bytes(), 5)
+        .expect("demo key is within the key length limits");
+    match xor_sharding.reconstruct_bytes(&xor_shards) {
+        Ok(combined) => println!("XOR split round trip matches: {}", combined == key.as_bytes()),
+        Err(e) => println!("XOR combine failed: {}", e),
+    }
+    
+    // Demonstrate sharding a passphrase-derived key
+    let mut salt = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let (passphrase_shards, derived_key) = sharding.shard_passphrase(
+        "correct horse battery staple", &salt, 5, Argon2Params::default())
+        .expect("derived keys are within the key length limits");
+    match sharding.reconstruct_bytes(&passphrase_shards[..3]) {
+        Ok(reconstructed) => println!("Passphrase key reconstruction matches: {}", reconstructed == derived_key),
+        Err(e) => println!("Passphrase key reconstruction failed: {}", e),
+    }
+    
+    // Demonstrate encrypting a shard for transport to its custodian
+    let mut transport_key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut transport_key);
+    let encrypted = shards[0].encrypt(&transport_key);
+    match encrypted.decrypt(&transport_key) {
+        Ok(decrypted) => println!("Encrypted shard round trip matches: {}", decrypted == shards[0]),
+        Err(e) => println!("Shard decryption failed: {}", e),
+    }
+    if let Err(e) = encrypted.decrypt(&[0u8; 32]) {
+        println!("Expected failure with the wrong transport key: {}", e);
+    }
+    
+    // Demonstrate a paper backup of one shard
+    let words = shards[0].to_mnemonic();
+    println!("Shard 1 as {} mnemonic words: {} ...", words.len(), words[..6].join(" "));
+    match timelock_key_sharing::mnemonic::decode_shard(&words) {
+        Ok(decoded) => println!("Mnemonic round trip matches: {}", decoded == shards[0]),
+        Err(e) => println!("Mnemonic decoding failed: {}", e),
+    }
+    
+    // Demonstrate reproducible sharding with a seeded RNG
+    let seeded_a = sharding.shard_key_with_rng(key.as_bytes(), 5, &mut ChaChaRng::from_seed([7u8; 32]));
+    let seeded_b = sharding.shard_key_with_rng(key.as_bytes(), 5, &mut ChaChaRng::from_seed([7u8; 32]));
+    println!("Seeded sharding is reproducible: {}", seeded_a.is_ok() && seeded_a.ok() == seeded_b.ok());
+    
+    // Try with insufficient shards
+    let insufficient = shards.iter().take(2).cloned().collect::<Vec<_>>();
+    match sharding.reconstruct_key(&insufficient) {
+        Ok(_) => {
+            println!("WARNING: Key was reconstructed with insufficient shards!");
+        },
+        Err(e) => {
+            println!("Expected failure with insufficient shards: {}", e);
+        }
+    }
+    
+    // Try with the same shard supplied twice
+    let duplicated = vec![shards[0].clone(), shards[0].clone(), shards[2].clone()];
+    match sharding.reconstruct_key(&duplicated) {
+        Ok(_) => {
+            println!("WARNING: Key was reconstructed from duplicated shards!");
+        },
+        Err(e) => {
+            println!("Expected failure with duplicated shards: {}", e);
+        }
+    }
+    
+    // Try with a tampered shard
+    let mut tampered = shards.iter().take(3).cloned().collect::<Vec<_>>();
+    let last = tampered[1].payload.len() - 1;
+    tampered[1].payload[last] ^= 0x01;
+    let mac_key = Shard::derive_mac_key(key.as_bytes());
+    match LCS35::for_unlock(sharding.difficulty()).unlock_verified(&tampered, &mac_key) {
+        Ok(_) => {
+            println!("WARNING: Key was reconstructed from a tampered shard!");
+        },
+        Err(e) => {
+            println!("Expected failure with tampered shard: {}", e);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+// Finite fields for Shamir sharing, and polynomial arithmetic generic over them
+//
+// `GF256` is the byte field every splitter shares keys over; `GFp` is a prime
+// field for schemes whose shares must not be reduced to bytes.
+
+use zeroize::Zeroize;
+
+pub trait FiniteField: Sized + Copy {
+    fn add(a: Self, b: Self) -> Self;
+    fn sub(a: Self, b: Self) -> Self;
+    fn mul(a: Self, b: Self) -> Self;
+    // `None` for zero, which has no multiplicative inverse
+    fn inv(a: Self) -> Option<Self>;
+
+    fn div(a: Self, b: Self) -> Option<Self> {
+        Some(Self::mul(a, Self::inv(b)?))
+    }
+}
+
+// An element of GF(2^8) under the AES polynomial x^8 + x^4 + x^3 + x + 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Zeroize)]
+pub struct GF256(pub u8);
+
+impl GF256 {
+    // The AES polynomial without its x^8 term, folded in when a product overflows
+    const REDUCTION_POLY: u8 = 0x1b;
+
+    // Lagrange basis polynomial of xs[i] over the x-coordinates `xs`, evaluated
+    // at zero: prod_{j != i} x_j / (x_j - x_i). Panics on a repeated x-coordinate.
+    pub fn lagrange_at_zero(xs: &[GF256], i: usize) -> GF256 {
+        xs.iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(GF256(1), |basis, (_, &x_j)| {
+                let factor = Self::div(x_j, Self::sub(x_j, xs[i])).expect("x-coordinates must be distinct");
+                Self::mul(basis, factor)
+            })
+    }
+}
+
+impl FiniteField for GF256 {
+    // Addition and subtraction are both XOR in characteristic 2
+    fn add(a: Self, b: Self) -> Self {
+        GF256(a.0 ^ b.0)
+    }
+
+    fn sub(a: Self, b: Self) -> Self {
+        GF256(a.0 ^ b.0)
+    }
+
+    fn mul(a: Self, b: Self) -> Self {
+        let (mut a, mut b) = (a.0, b.0);
+        let mut product = 0u8;
+        while b != 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= Self::REDUCTION_POLY;
+            }
+            b >>= 1;
+        }
+        GF256(product)
+    }
+
+    // a^254, since a^255 = 1 for every non-zero a
+    fn inv(a: Self) -> Option<Self> {
+        if a.0 == 0 {
+            return None;
+        }
+        let mut result = GF256(1);
+        let mut base = a;
+        let mut exp = 254u8;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = Self::mul(result, base);
+            }
+            base = Self::mul(base, base);
+            exp >>= 1;
+        }
+        Some(result)
+    }
+}
+
+// An element of GF(p). Both operands of an operation must share the same
+// prime, which must be below 2^63 so sums cannot overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Zeroize)]
+pub struct GFp {
+    value: u64,
+    prime: u64,
+}
+
+impl GFp {
+    // `value` is reduced modulo `prime`. The primality of `prime` is not checked;
+    // with a composite modulus `inv` gives wrong answers.
+    pub fn new(value: u64, prime: u64) -> Self {
+        assert!((2..1 << 63).contains(&prime), "prime must be between 2 and 2^63");
+        GFp { value: value % prime, prime }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn prime(self) -> u64 {
+        self.prime
+    }
+
+    fn pow(self, mut exp: u64) -> Self {
+        let mut result = GFp { value: 1 % self.prime, prime: self.prime };
+        let mut base = self;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = Self::mul(result, base);
+            }
+            base = Self::mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+impl FiniteField for GFp {
+    fn add(a: Self, b: Self) -> Self {
+        debug_assert_eq!(a.prime, b.prime, "operands belong to different fields");
+        GFp { value: (a.value + b.value) % a.prime, prime: a.prime }
+    }
+
+    fn sub(a: Self, b: Self) -> Self {
+        debug_assert_eq!(a.prime, b.prime, "operands belong to different fields");
+        GFp { value: (a.value + a.prime - b.value) % a.prime, prime: a.prime }
+    }
+
+    fn mul(a: Self, b: Self) -> Self {
+        debug_assert_eq!(a.prime, b.prime, "operands belong to different fields");
+        let product = a.value as u128 * b.value as u128 % a.prime as u128;
+        GFp { value: product as u64, prime: a.prime }
+    }
+
+    // a^(p-2), since a^(p-1) = 1 for every non-zero a
+    fn inv(a: Self) -> Option<Self> {
+        (a.value != 0).then(|| a.pow(a.prime - 2))
+    }
+}
+
+// Evaluate a polynomial given its coefficients in ascending degree order
+// (Horner's rule). Panics on an empty coefficient list.
+pub fn eval_poly<F: FiniteField>(coefficients: &[F], x: F) -> F {
+    let (&leading, rest) = coefficients.split_last().expect("a polynomial has at least one coefficient");
+    rest.iter()
+        .rev()
+        .fold(leading, |acc, &coefficient| F::add(F::mul(acc, x), coefficient))
+}
+
+// Evaluate the polynomial defined by (x, y) points at x = 0 using Lagrange
+// interpolation. Panics on an empty point list or a repeated x-coordinate.
+pub fn interpolate_at_zero<F: FiniteField>(points: &[(F, F)]) -> F {
+    let mut secret = None;
+    for (i, &(x_i, y_i)) in points.iter().enumerate() {
+        // y_i * l_i(0), where l_i(0) = prod_{j != i} x_j / (x_j - x_i)
+        let mut term = y_i;
+        for (j, &(x_j, _)) in points.iter().enumerate() {
+            if i != j {
+                let basis = F::div(x_j, F::sub(x_j, x_i)).expect("x-coordinates must be distinct");
+                term = F::mul(term, basis);
+            }
+        }
+        secret = Some(secret.map_or(term, |sum| F::add(sum, term)));
+    }
+    secret.expect("interpolation needs at least one point")
+}
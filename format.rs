@@ -0,0 +1,164 @@
+// Binary shard layouts, one decoder per format version
+//
+//   v1  [version, index, total, threshold, payload_len (u32 LE)] payload trailer
+//   v2  v1's header, checksum (u32 LE), payload trailer
+//   v3  v2's header, flags, payload, mac?, release_timestamp (u64 LE)?
+//
+// v1 and v2 shards carry their optional MAC and release time as an untagged
+// trailer told apart by its length: the two have different lengths, so any
+// combination of them is unambiguous. v3 flags which of them follow instead.
+
+use crate::error::TimelockError;
+use crate::maturity::MaturityClock;
+use crate::shard::{Shard, CURRENT_VERSION, MAC_LEN, MATURITY_LEN};
+
+// Current headers: v2's plus the flags byte
+pub(crate) const HEADER_LEN: usize = 13;
+
+// Version 2 headers end after the checksum
+const V2_HEADER_LEN: usize = 12;
+
+// Version 1 headers end after the payload length
+const V1_HEADER_LEN: usize = 8;
+
+// v3 flags
+const FLAG_MAC: u8 = 0x01;
+const FLAG_MATURITY: u8 = 0x02;
+
+// Length of the binary header for a shard format version
+pub(crate) fn header_len(version: u8) -> usize {
+    match version {
+        0 | 1 => V1_HEADER_LEN,
+        2 => V2_HEADER_LEN,
+        _ => HEADER_LEN,
+    }
+}
+
+// Encode in the layout of the shard's own version, so a shard read from an
+// older format is written back the same way
+pub(crate) fn encode(shard: &Shard) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + shard.payload.len() + MAC_LEN + MATURITY_LEN);
+    bytes.push(shard.version);
+    bytes.push(shard.index);
+    bytes.push(shard.total);
+    bytes.push(shard.threshold);
+    bytes.extend_from_slice(&(shard.payload.len() as u32).to_le_bytes());
+    if shard.version >= 2 {
+        bytes.extend_from_slice(&shard.checksum.to_le_bytes());
+    }
+    if shard.version >= 3 {
+        let mut flags = 0;
+        if shard.mac.is_some() {
+            flags |= FLAG_MAC;
+        }
+        if shard.maturity_clock.is_some() {
+            flags |= FLAG_MATURITY;
+        }
+        bytes.push(flags);
+    }
+    bytes.extend_from_slice(&shard.payload);
+    if let Some(mac) = &shard.mac {
+        bytes.extend_from_slice(mac);
+    }
+    if let Some(clock) = &shard.maturity_clock {
+        bytes.extend_from_slice(&clock.release_timestamp().to_le_bytes());
+    }
+    bytes
+}
+
+// Dispatch on the version byte
+pub(crate) fn decode(b: &[u8]) -> Result<Shard, TimelockError> {
+    match b.first() {
+        None => Err(TimelockError::InvalidShardFormat("no shard bytes".to_string())),
+        Some(1) => decode_v1(b),
+        Some(2) => decode_v2(b),
+        Some(3) => decode_v3(b),
+        Some(&version) => Err(TimelockError::InvalidShardFormat(format!(
+            "unsupported shard version {}, expected 1 to {}", version, CURRENT_VERSION))),
+    }
+}
+
+// Version 1 predates checksums, so the checksum is computed from the payload
+// and there is nothing stored to check it against
+pub(crate) fn decode_v1(b: &[u8]) -> Result<Shard, TimelockError> {
+    let (payload, trailer) = split_body(b, V1_HEADER_LEN)?;
+    let (mac, maturity_clock) = untagged_trailer(trailer)?;
+    Ok(shard_from_header(b, payload, crc32fast::hash(payload), mac, maturity_clock))
+}
+
+pub(crate) fn decode_v2(b: &[u8]) -> Result<Shard, TimelockError> {
+    let (payload, trailer) = split_body(b, V2_HEADER_LEN)?;
+    let (mac, maturity_clock) = untagged_trailer(trailer)?;
+    let checksum = u32::from_le_bytes([b[8], b[9], b[10], b[11]]);
+    Ok(shard_from_header(b, payload, checksum, mac, maturity_clock))
+}
+
+pub(crate) fn decode_v3(b: &[u8]) -> Result<Shard, TimelockError> {
+    let (payload, trailer) = split_body(b, HEADER_LEN)?;
+    let flags = b[12];
+    if flags & !(FLAG_MAC | FLAG_MATURITY) != 0 {
+        return Err(TimelockError::InvalidShardFormat(format!("unknown shard flags {:#04x}", flags)));
+    }
+
+    let mac_len = if flags & FLAG_MAC != 0 { MAC_LEN } else { 0 };
+    let maturity_len = if flags & FLAG_MATURITY != 0 { MATURITY_LEN } else { 0 };
+    if trailer.len() != mac_len + maturity_len {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "flags call for {} bytes after the payload, found {}", mac_len + maturity_len, trailer.len())));
+    }
+
+    let (mac, maturity) = trailer.split_at(mac_len);
+    let mac = (mac_len > 0).then(|| <[u8; MAC_LEN]>::try_from(mac).expect("trailer split at MAC_LEN"));
+    let maturity_clock = (maturity_len > 0).then(|| release_clock(maturity));
+    let checksum = u32::from_le_bytes([b[8], b[9], b[10], b[11]]);
+    Ok(shard_from_header(b, payload, checksum, mac, maturity_clock))
+}
+
+// Split what follows a `header_len`-byte header into the payload its length
+// field declares and whatever trails it
+fn split_body(b: &[u8], header_len: usize) -> Result<(&[u8], &[u8]), TimelockError> {
+    if b.len() < header_len {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "expected at least {} header bytes, found {}", header_len, b.len())));
+    }
+
+    let payload_len = u32::from_le_bytes([b[4], b[5], b[6], b[7]]) as usize;
+    let body = &b[header_len..];
+    if body.len() < payload_len {
+        return Err(TimelockError::InvalidShardFormat(format!(
+            "header declares a {}-byte payload, found {} bytes", payload_len, body.len())));
+    }
+    Ok(body.split_at(payload_len))
+}
+
+fn untagged_trailer(trailer: &[u8]) -> Result<(Option<[u8; MAC_LEN]>, Option<MaturityClock>), TimelockError> {
+    let (mac, maturity) = match trailer.len() {
+        0 => (None, None),
+        MATURITY_LEN => (None, Some(trailer)),
+        MAC_LEN => (Some(trailer), None),
+        n if n == MAC_LEN + MATURITY_LEN => (Some(&trailer[..MAC_LEN]), Some(&trailer[MAC_LEN..])),
+        n => return Err(TimelockError::InvalidShardFormat(format!(
+            "expected an optional {}-byte MAC and {}-byte release time after the payload, found {} bytes",
+            MAC_LEN, MATURITY_LEN, n))),
+    };
+    let mac = mac.map(|mac| <[u8; MAC_LEN]>::try_from(mac).expect("trailer split at MAC_LEN"));
+    Ok((mac, maturity.map(release_clock)))
+}
+
+fn release_clock(timestamp: &[u8]) -> MaturityClock {
+    MaturityClock::new(u64::from_le_bytes(timestamp.try_into().expect("trailer split at MATURITY_LEN")))
+}
+
+fn shard_from_header(b: &[u8], payload: &[u8], checksum: u32, mac: Option<[u8; MAC_LEN]>,
+                     maturity_clock: Option<MaturityClock>) -> Shard {
+    Shard {
+        version: b[0],
+        index: b[1],
+        total: b[2],
+        threshold: b[3],
+        payload: payload.to_vec(),
+        checksum,
+        mac,
+        maturity_clock,
+    }
+}
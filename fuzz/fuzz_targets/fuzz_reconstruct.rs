@@ -0,0 +1,18 @@
+#![no_main]
+
+// Feed arbitrary input to `reconstruct_bytes` the way an untrusted caller would:
+// as hex-encoded shards, one per newline-separated chunk. It may return `Err`,
+// but must never panic.
+
+use libfuzzer_sys::fuzz_target;
+use timelock_key_sharing::{Shard, TimelockKeySharding};
+
+fuzz_target!(|data: &[u8]| {
+    let shards: Vec<Shard> = data
+        .split(|&byte| byte == b'\n')
+        .filter_map(|chunk| Shard::from_hex(&hex::encode(chunk)).ok())
+        .collect();
+
+    // Difficulty 1 keeps each run fast; unlocking generates no modulus
+    let _ = TimelockKeySharding::new(1, 2).reconstruct_bytes(&shards);
+});
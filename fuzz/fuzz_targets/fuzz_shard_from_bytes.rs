@@ -0,0 +1,15 @@
+#![no_main]
+
+// `Shard::from_bytes` and `Shard::from_hex` must reject malformed input with an
+// error rather than panic, and every shard they accept must survive a round trip.
+
+use libfuzzer_sys::fuzz_target;
+use timelock_key_sharing::Shard;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(shard) = Shard::from_bytes(data) {
+        assert_eq!(Shard::from_bytes(&shard.to_bytes()).unwrap(), shard);
+    }
+
+    let _ = Shard::from_hex(&String::from_utf8_lossy(data));
+});
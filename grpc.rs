@@ -0,0 +1,97 @@
+// gRPC service over a `StorageBackend`, generated from proto/schrödinger.proto
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use crate::storage::StorageBackend;
+use crate::TimelockKeySharding;
+use std::io;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("schrodinger");
+}
+
+use proto::timelock_shard_server::TimelockShard;
+use proto::{
+    FetchShardRequest, FetchShardResponse, ReconstructRequest, ReconstructResponse, StoreShardRequest,
+    StoreShardResponse,
+};
+
+// Serve it with `proto::timelock_shard_server::TimelockShardServer::new(server)`
+#[derive(Debug)]
+pub struct TimelockShardServer<B> {
+    backend: Arc<B>,
+}
+
+impl<B> TimelockShardServer<B> {
+    pub fn new(backend: B) -> Self {
+        TimelockShardServer { backend: Arc::new(backend) }
+    }
+
+    // Run a backend call on tokio's blocking pool, since every backend does
+    // synchronous I/O
+    async fn with_backend<T, F>(&self, call: F) -> Result<T, Status>
+    where
+        B: Send + Sync + 'static,
+        T: Send + 'static,
+        F: FnOnce(&B) -> Result<T, TimelockError> + Send + 'static,
+    {
+        let backend = Arc::clone(&self.backend);
+        tokio::task::spawn_blocking(move || call(backend.as_ref())).await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(to_status)
+    }
+}
+
+#[tonic::async_trait]
+impl<B> TimelockShard for TimelockShardServer<B>
+where
+    B: StorageBackend + Send + Sync + 'static,
+{
+    async fn store_shard(&self, request: Request<StoreShardRequest>) -> Result<Response<StoreShardResponse>, Status> {
+        let shard = Shard::from_bytes(&request.into_inner().shard).map_err(to_status)?;
+        self.with_backend(move |backend| backend.store_shard(&shard)).await?;
+        Ok(Response::new(StoreShardResponse {}))
+    }
+
+    async fn fetch_shard(&self, request: Request<FetchShardRequest>) -> Result<Response<FetchShardResponse>, Status> {
+        let index = u8::try_from(request.into_inner().index)
+            .map_err(|_| Status::invalid_argument("shard index must fit in a byte"))?;
+        let shard = self.with_backend(move |backend| backend.load_shard(index)).await?;
+        Ok(Response::new(FetchShardResponse { shard: shard.to_bytes() }))
+    }
+
+    async fn reconstruct_key(&self, request: Request<ReconstructRequest>) -> Result<Response<ReconstructResponse>, Status> {
+        let request = request.into_inner();
+        let threshold = request.threshold as usize;
+        let supplied = request.shards.iter()
+            .map(|bytes| Shard::from_bytes(bytes))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(to_status)?;
+
+        let key = self.with_backend(move |backend| {
+            let shards = if supplied.is_empty() {
+                backend.list_shards()?
+                    .into_iter()
+                    .map(|index| backend.load_shard(index))
+                    .collect::<Result<Vec<_>, _>>()?
+            } else {
+                supplied
+            };
+            TimelockKeySharding::new(request.difficulty, threshold).reconstruct_bytes(&shards)
+        }).await?;
+        Ok(Response::new(ReconstructResponse { key }))
+    }
+}
+
+fn to_status(e: TimelockError) -> Status {
+    match &e {
+        TimelockError::Io(io_error) if io_error.kind() == io::ErrorKind::NotFound => Status::not_found(e.to_string()),
+        TimelockError::InvalidShardFormat(_) | TimelockError::CorruptedShard { .. }
+            | TimelockError::DuplicateShard { .. } | TimelockError::InsufficientShards { .. }
+            | TimelockError::InvalidConfiguration(_) => Status::invalid_argument(e.to_string()),
+        TimelockError::RateLimited { .. } => Status::resource_exhausted(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    }
+}
@@ -0,0 +1,57 @@
+// Hash functions available for expanding a puzzle solution into a keystream
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    // Identifier recorded in each shard's payload
+    pub fn id(self) -> u8 {
+        match self {
+            HashAlgorithm::Sha256 => 0,
+            HashAlgorithm::Blake3 => 1,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(HashAlgorithm::Sha256),
+            1 => Some(HashAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    // Expand `seed` into `len` keystream bytes: SHA-256 in counter mode, or
+    // Blake3's extendable output
+    pub(crate) fn keystream(self, seed: &[u8], len: usize) -> Zeroizing<Vec<u8>> {
+        match self {
+            HashAlgorithm::Sha256 => {
+                let mut keystream = Zeroizing::new(Vec::with_capacity(len + 32));
+                let mut counter = 0u32;
+                while keystream.len() < len {
+                    let mut hasher = Sha256::new();
+                    hasher.update(seed);
+                    hasher.update(counter.to_le_bytes());
+                    keystream.extend_from_slice(&hasher.finalize());
+                    counter += 1;
+                }
+                keystream.truncate(len);
+                keystream
+            }
+            HashAlgorithm::Blake3 => {
+                let mut keystream = Zeroizing::new(vec![0u8; len]);
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(seed);
+                hasher.finalize_xof().fill(&mut keystream);
+                keystream
+            }
+        }
+    }
+}
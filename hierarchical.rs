@@ -0,0 +1,159 @@
+// Hierarchical sharding: the key is split among groups, each group's share is
+// split again among its members, and so on for every configured level
+//
+// Every level uses plain GF(256) Shamir sharing as in `ShamirSplit`, so the
+// shards are not timelocked. A node's secret is its own share of its parent's
+// secret; only the leaves of the tree become `Shard`s. Each leaf records the
+// x-coordinates of its ancestors so reconstruction can regroup the leaves.
+// Payload layout: [x, depth, ancestor x-coordinates from the top, y_0, y_1, ...]
+
+use crate::error::TimelockError;
+use crate::secret_splitter::{check_split_params, combine_bytes, share_bytes};
+use crate::shard::Shard;
+use rand::RngCore;
+use std::collections::{BTreeMap, HashSet};
+use zeroize::Zeroizing;
+
+// (x, share) pairs of each node's secret, keyed by the node's path from the root
+type NodeShares = BTreeMap<Vec<u8>, Vec<(u8, Zeroizing<Vec<u8>>)>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelConfig {
+    pub num_shards: usize,
+    pub threshold: usize,
+}
+
+// Levels from the top down: `levels[0]` splits the key among the groups.
+// Deserialized configurations are checked as in `new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "UncheckedHierarchy"))]
+pub struct HierarchicalSharding {
+    levels: Vec<LevelConfig>,
+}
+
+// Serde's view of a configuration before `new` has accepted it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedHierarchy {
+    levels: Vec<LevelConfig>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedHierarchy> for HierarchicalSharding {
+    type Error = TimelockError;
+
+    fn try_from(unchecked: UncheckedHierarchy) -> Result<Self, Self::Error> {
+        HierarchicalSharding::new(unchecked.levels)
+    }
+}
+
+impl HierarchicalSharding {
+    pub fn new(levels: Vec<LevelConfig>) -> Result<Self, TimelockError> {
+        if levels.is_empty() {
+            return Err(TimelockError::InvalidConfiguration("at least one level is required".to_string()));
+        }
+        for level in &levels {
+            check_split_params(level.num_shards, level.threshold)?;
+        }
+        Ok(HierarchicalSharding { levels })
+    }
+
+    pub fn levels(&self) -> &[LevelConfig] {
+        &self.levels
+    }
+}
+
+// Shard `key` down the tree, returning the leaf shards grouped by top-level group
+pub fn shard_hierarchical(key: &[u8], config: &HierarchicalSharding) -> Vec<Vec<Shard>> {
+    let mut groups = vec![Vec::new(); config.levels[0].num_shards];
+    split_node(key, config, &mut Vec::new(), &mut rand::thread_rng(), &mut groups);
+    groups
+}
+
+fn split_node(secret: &[u8], config: &HierarchicalSharding, path: &mut Vec<u8>, rng: &mut impl RngCore,
+              groups: &mut [Vec<Shard>]) {
+    let level = config.levels[path.len()];
+    for (x, values) in (1..=level.num_shards as u8).zip(share_bytes(secret, level.num_shards, level.threshold, rng)) {
+        if path.len() + 1 < config.levels.len() {
+            path.push(x);
+            split_node(&values, config, path, rng, groups);
+            path.pop();
+            continue;
+        }
+
+        let mut payload = Vec::with_capacity(2 + path.len() + values.len());
+        payload.push(x);
+        payload.push(path.len() as u8);
+        payload.extend_from_slice(path);
+        payload.extend_from_slice(&values);
+        let group = path.first().copied().unwrap_or(x) as usize - 1;
+        groups[group].push(Shard::new(x, level.num_shards as u8, level.threshold as u8, payload));
+    }
+}
+
+// Rebuild the key bottom-up: every node with at least its level's threshold of
+// surviving children is recovered, until the root is. Groups may be incomplete
+// or missing as long as enough of each level survives.
+pub fn reconstruct_hierarchical(shards: Vec<Vec<Shard>>, config: &HierarchicalSharding)
+    -> Result<Vec<u8>, TimelockError>
+{
+    let depth = config.levels.len() - 1;
+
+    let mut nodes = NodeShares::new();
+    for (index, shard) in shards.iter().flatten().enumerate() {
+        let [x, shard_depth, rest @ ..] = shard.payload.as_slice() else {
+            return Err(TimelockError::CorruptedShard { index });
+        };
+        if *x == 0 || *shard_depth as usize != depth || rest.len() < depth {
+            return Err(TimelockError::CorruptedShard { index });
+        }
+        let (path, values) = rest.split_at(depth);
+        nodes.entry(path.to_vec()).or_default().push((*x, Zeroizing::new(values.to_vec())));
+    }
+
+    for level in config.levels.iter().rev() {
+        let mut parents = NodeShares::new();
+        for (mut path, shares) in nodes {
+            if let Some(secret) = combine_node(&shares, level.threshold)? {
+                let x = path.pop().unwrap_or(0);
+                parents.entry(path).or_default().push((x, secret));
+            }
+        }
+        nodes = parents;
+    }
+
+    // Above the top level the only node is the root, with the key as its x = 0 "share"
+    nodes.remove(&Vec::new())
+        .and_then(|mut root| root.pop())
+        .map(|(_, key)| key.to_vec())
+        .ok_or(TimelockError::InsufficientShards {
+            provided: shards.iter().map(Vec::len).sum(),
+            required: config.levels.iter().map(|level| level.threshold).product(),
+        })
+}
+
+// Interpolate one node's secret from its children's shares, or `None` if too
+// few children survive
+fn combine_node(shares: &[(u8, Zeroizing<Vec<u8>>)], threshold: usize)
+    -> Result<Option<Zeroizing<Vec<u8>>>, TimelockError>
+{
+    let mut seen = HashSet::with_capacity(shares.len());
+    for (x, _) in shares {
+        if !seen.insert(*x) {
+            return Err(TimelockError::DuplicateShard { index: *x });
+        }
+    }
+    if shares.len() < threshold {
+        return Ok(None);
+    }
+
+    let shares = &shares[..threshold];
+    let len = shares[0].1.len();
+    if shares.iter().any(|(_, values)| values.len() != len) {
+        return Err(TimelockError::InvalidShardFormat("shares of one node differ in length".to_string()));
+    }
+
+    let secret = Zeroizing::new(combine_bytes(shares));
+    Ok(Some(secret))
+}
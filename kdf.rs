@@ -0,0 +1,86 @@
+// Passphrase-based key derivation with Argon2id, and per-shard transport keys
+// derived from a master key with HKDF
+
+use crate::error::TimelockError;
+use argon2::{Algorithm, Argon2, Params, Version};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Argon2Params {
+    // Memory size in KiB
+    pub m_cost: u32,
+    // Number of passes over memory
+    pub t_cost: u32,
+    // Degree of parallelism
+    pub p_cost: u32,
+}
+
+impl Default for Argon2Params {
+    // The argon2 crate's defaults, following the OWASP recommendation
+    fn default() -> Self {
+        Argon2Params {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Argon2Params {
+    // `InvalidConfiguration` unless every cost is within the range Argon2 accepts
+    pub fn check(&self) -> Result<(), TimelockError> {
+        self.to_argon2().map(|_| ())
+    }
+
+    fn to_argon2(self) -> Result<Params, TimelockError> {
+        Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32)).map_err(|e| TimelockError::InvalidConfiguration(
+            format!("Argon2 costs m={} t={} p={} are out of range: {}", self.m_cost, self.t_cost, self.p_cost, e)))
+    }
+}
+
+// Derive a 32-byte key from `passphrase`. Panics if the cost parameters are
+// outside the ranges Argon2 accepts; see `Argon2Params::check`.
+pub fn derive_key_from_passphrase(passphrase: &str, salt: &[u8; 32], params: Argon2Params) -> [u8; 32] {
+    let params = params.to_argon2().expect("Argon2 cost parameters are out of range");
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("a 32-byte salt and output are always accepted");
+    key
+}
+
+// Derives a distinct transport key for every shard from one master key, so a
+// custodian holding their own shard key learns nothing about anyone else's.
+// The master key is wiped on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct KeyDerivationRatchet {
+    master_key: [u8; 32],
+}
+
+impl KeyDerivationRatchet {
+    pub fn new(master_key: [u8; 32]) -> Self {
+        KeyDerivationRatchet { master_key }
+    }
+
+    // HKDF-SHA256(master_key, info = "shard-{index}"), with no salt
+    pub fn derive_shard_key(&self, index: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, &self.master_key)
+            .expand(format!("shard-{}", index).as_bytes(), &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        key
+    }
+}
+
+// Never prints the master key
+impl fmt::Debug for KeyDerivationRatchet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyDerivationRatchet").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,117 @@
+// Step-by-step key ceremony with every custodian in the room
+//
+//   1. the dealer generates the key and shards it, one shard per participant in
+//      the order they are listed;
+//   2. a commitment to every shard is published before any shard changes hands;
+//   3. each participant checks the shard they receive against their published
+//      commitment and accepts it;
+//   4. the ceremony is finalized into a record for the minutes.
+
+use crate::audit;
+use crate::ceremony::{commit_shard, verify_commitment, ShardCommitment};
+use crate::error::TimelockError;
+use crate::secret_splitter::check_split_params;
+use crate::shard::Shard;
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+
+// Length of the key generated in step 1
+pub const CEREMONY_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct ParticipantId(pub u32);
+
+#[derive(Debug, Clone)]
+pub struct KeyCeremony {
+    participants: Vec<ParticipantId>,
+    threshold: usize,
+    key_generated: bool,
+    // Published in step 2; the i-th belongs to the i-th participant
+    commitments: Vec<ShardCommitment>,
+    accepted: Vec<ParticipantId>,
+}
+
+// Outcome of a ceremony
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CeremonyRecord {
+    pub participants: Vec<ParticipantId>,
+    pub threshold: usize,
+    pub commitments: Vec<ShardCommitment>,
+    // Participants whose shard matched their commitment, in acceptance order
+    pub accepted: Vec<ParticipantId>,
+    // Every participant accepted their shard
+    pub complete: bool,
+    // Unix seconds at which the record was made
+    pub finalized_at: u64,
+}
+
+impl KeyCeremony {
+    // Fails unless 2 <= threshold <= participants.len(), the split the
+    // dealer's shards will have to satisfy
+    pub fn new(participants: Vec<ParticipantId>, threshold: usize) -> Result<Self, TimelockError> {
+        check_split_params(participants.len(), threshold)?;
+        Ok(KeyCeremony {
+            participants,
+            threshold,
+            key_generated: false,
+            commitments: Vec::new(),
+            accepted: Vec::new(),
+        })
+    }
+
+    // Draw a fresh 32-byte key for the dealer to shard, one shard per
+    // participant. The caller owns the key and must wipe it once sharded.
+    pub fn step1_generate_key(&mut self, rng: &mut impl RngCore) -> Vec<u8> {
+        let mut key = vec![0u8; CEREMONY_KEY_LEN];
+        rng.fill_bytes(&mut key);
+        self.key_generated = true;
+        key
+    }
+
+    // Commit to `shards`, which must be in participant order. Committing again
+    // replaces the earlier commitments and every acceptance based on them.
+    pub fn step2_commit_shards(&mut self, shards: &[Shard]) -> Vec<ShardCommitment> {
+        self.commitments = shards.iter().map(commit_shard).collect();
+        self.accepted.clear();
+        self.commitments.clone()
+    }
+
+    // Accept `shard` for `participant` if `commitment` is the one published for
+    // them in step 2 and the shard matches it
+    pub fn step3_verify_and_accept(&mut self, participant: ParticipantId, shard: &Shard,
+                                   commitment: &ShardCommitment) -> Result<(), TimelockError> {
+        let position = self.participants.iter()
+            .position(|&p| p == participant)
+            .ok_or_else(|| TimelockError::InvalidConfiguration(format!(
+                "participant {} is not part of this ceremony", participant.0)))?;
+        let published = self.commitments.get(position)
+            .ok_or_else(|| TimelockError::InvalidConfiguration(format!(
+                "no commitment was published for participant {}", participant.0)))?;
+        if self.accepted.contains(&participant) {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "participant {} has already accepted a shard", participant.0)));
+        }
+
+        let published_matches: bool = published.ct_eq(commitment).into();
+        if !published_matches || !verify_commitment(shard, commitment) {
+            return Err(TimelockError::CorruptedShard { index: position });
+        }
+        self.accepted.push(participant);
+        Ok(())
+    }
+
+    pub fn finalize(&self) -> CeremonyRecord {
+        CeremonyRecord {
+            participants: self.participants.clone(),
+            threshold: self.threshold,
+            commitments: self.commitments.clone(),
+            accepted: self.accepted.clone(),
+            complete: self.key_generated
+                && self.commitments.len() == self.participants.len()
+                && self.accepted.len() == self.participants.len(),
+            finalized_at: audit::now(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Calendar release dates for shards
+//
+// Unlike the timelock puzzle, which holds a shard for as long as the squarings
+// take, a maturity clock is only a policy: anyone holding the shard bytes can
+// strip it. It is not covered by the shard MAC.
+
+use crate::audit::now;
+use std::time::Duration;
+use zeroize::Zeroize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Zeroize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaturityClock {
+    // Seconds since the Unix epoch at which the shard may be released
+    release_timestamp: u64,
+}
+
+impl MaturityClock {
+    pub fn new(release_timestamp: u64) -> Self {
+        MaturityClock { release_timestamp }
+    }
+
+    // Clock maturing `delay` from now
+    pub fn after(delay: Duration) -> Self {
+        MaturityClock::new(now().saturating_add(delay.as_secs()))
+    }
+
+    pub fn release_timestamp(&self) -> u64 {
+        self.release_timestamp
+    }
+
+    pub fn is_mature(&self) -> bool {
+        now() >= self.release_timestamp
+    }
+
+    // Time left until release, or `None` once the clock has matured
+    pub fn time_remaining(&self) -> Option<Duration> {
+        self.release_timestamp.checked_sub(now())
+            .filter(|&seconds| seconds > 0)
+            .map(Duration::from_secs)
+    }
+}
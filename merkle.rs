@@ -0,0 +1,115 @@
+// Merkle tree over a shard set, so a root published once lets each custodian
+// later show their shard belonged to the original set
+//
+// Leaves are SHA256(0x00 || shard.to_bytes()) and inner nodes SHA256(0x01 ||
+// left || right); the prefixes keep a leaf from passing as an inner node. A node
+// without a sibling moves up a level unchanged.
+
+use crate::shard::Shard;
+use sha2::{Digest, Sha256};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    // levels[0] holds the leaves in shard order, the last level the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MerkleProof {
+    // Position of the shard's leaf
+    pub index: usize,
+    // Sibling hashes from the leaf upwards, skipping levels where the node had none
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<Vec<serde_with::hex::Hex>>"))]
+    pub siblings: Vec<[u8; 32]>,
+    // Number of leaves, which fixes where siblings are missing
+    pub leaf_count: usize,
+}
+
+pub fn build_shard_merkle_tree(shards: &[Shard]) -> MerkleTree {
+    let mut levels = vec![shards.iter().map(leaf_hash).collect::<Vec<_>>()];
+    while levels.last().expect("at least the leaf level").len() > 1 {
+        let next = levels.last().expect("at least the leaf level")
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            })
+            .collect();
+        levels.push(next);
+    }
+    MerkleTree { levels }
+}
+
+impl MerkleTree {
+    // SHA-256 of nothing for an empty set
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_else(|| Sha256::digest([]).into())
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    // Panics if `index` is not the position of a shard in the tree
+    pub fn proof(&self, index: usize) -> MerkleProof {
+        assert!(index < self.leaf_count(), "no shard at position {} of {}", index, self.leaf_count());
+
+        let mut siblings = Vec::with_capacity(self.levels.len());
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        MerkleProof { index, siblings, leaf_count: self.leaf_count() }
+    }
+}
+
+impl MerkleProof {
+    // Whether `shard` is the leaf at `index` of the tree with `root`
+    pub fn verify(&self, root: &[u8; 32], shard: &Shard) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+
+        let mut hash = leaf_hash(shard);
+        let mut siblings = self.siblings.iter();
+        let (mut position, mut width) = (self.index, self.leaf_count);
+        while width > 1 {
+            let has_sibling = position ^ 1 < width;
+            if has_sibling {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                hash = if position.is_multiple_of(2) { node_hash(&hash, sibling) } else { node_hash(sibling, &hash) };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == *root
+    }
+}
+
+fn leaf_hash(shard: &Shard) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(shard.to_bytes());
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
@@ -0,0 +1,14 @@
+// Upgrading stored shards to newer binary format versions
+
+use crate::shard::Shard;
+
+// Version 1 shard in the version 2 format, with the CRC32 checksum of its
+// payload. Shards of any other version are returned unchanged, so a directory
+// of mixed versions can be migrated in one pass.
+pub fn migrate_v1_to_v2(mut shard: Shard) -> Shard {
+    if shard.version == 1 {
+        shard.version = 2;
+        shard.checksum = crc32fast::hash(&shard.payload);
+    }
+    shard
+}
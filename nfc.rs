@@ -0,0 +1,80 @@
+// Splitting a shard across NFC NDEF records for tap-to-transfer ceremonies
+//
+// Each record is [total_chunks, chunk_index, session_id (4 bytes), chunk...],
+// where the chunks in index order concatenate to `Shard::to_bytes`. The session
+// id is the first four bytes of SHA-256 over those bytes, so records of
+// different shards are never mixed and a bad reassembly is caught.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use sha2::{Digest, Sha256};
+
+const RECORD_HEADER_LEN: usize = 6;
+
+// Panics if `max_record_size` leaves no room for data after the header, or if
+// the shard would need more than 255 records
+pub fn split_for_nfc(shard: &Shard, max_record_size: usize) -> Vec<Vec<u8>> {
+    if max_record_size <= RECORD_HEADER_LEN {
+        panic!("NFC records must be larger than the {}-byte chunk header", RECORD_HEADER_LEN);
+    }
+
+    let bytes = shard.to_bytes();
+    let chunks: Vec<&[u8]> = bytes.chunks(max_record_size - RECORD_HEADER_LEN).collect();
+    if chunks.len() > u8::MAX as usize {
+        panic!("shard needs {} NFC records, more than the 255 the header can count", chunks.len());
+    }
+
+    let session_id = session_id(&bytes);
+    chunks.iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut record = Vec::with_capacity(RECORD_HEADER_LEN + chunk.len());
+            record.push(chunks.len() as u8);
+            record.push(index as u8);
+            record.extend_from_slice(&session_id);
+            record.extend_from_slice(chunk);
+            record
+        })
+        .collect()
+}
+
+// Records may arrive in any order, but every one of them must be present
+pub fn reassemble_from_nfc(records: &[Vec<u8>]) -> Result<Shard, TimelockError> {
+    let invalid = |msg: String| TimelockError::InvalidShardFormat(msg);
+
+    let first = records.first().ok_or_else(|| invalid("no NFC records".to_string()))?;
+    if first.len() < RECORD_HEADER_LEN {
+        return Err(invalid("NFC record is shorter than its header".to_string()));
+    }
+    let total = first[0] as usize;
+    let session: [u8; 4] = first[2..RECORD_HEADER_LEN].try_into().expect("header holds a 4-byte session id");
+
+    let mut chunks: Vec<Option<&[u8]>> = vec![None; total];
+    for record in records {
+        if record.len() < RECORD_HEADER_LEN {
+            return Err(invalid("NFC record is shorter than its header".to_string()));
+        }
+        if record[0] as usize != total || record[2..RECORD_HEADER_LEN] != session {
+            return Err(invalid("NFC records come from different shards".to_string()));
+        }
+        let slot = chunks.get_mut(record[1] as usize)
+            .ok_or_else(|| invalid(format!("NFC record index {} is out of range", record[1])))?;
+        if slot.replace(&record[RECORD_HEADER_LEN..]).is_some() {
+            return Err(invalid(format!("NFC record {} was provided more than once", record[1])));
+        }
+    }
+
+    let mut bytes = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        bytes.extend_from_slice(chunk.ok_or_else(|| invalid(format!("NFC record {} of {} is missing", index, total)))?);
+    }
+    if session_id(&bytes) != session {
+        return Err(invalid("reassembled NFC records do not match their session id".to_string()));
+    }
+    Shard::from_bytes(&bytes)
+}
+
+fn session_id(bytes: &[u8]) -> [u8; 4] {
+    let digest = Sha256::digest(bytes);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
@@ -0,0 +1,76 @@
+// PEM armor for shards, in the style of PGP armored blocks
+//
+//   -----BEGIN SCHRÖDINGER SHARD-----
+//   Version: 1
+//   Index: 2
+//
+//   <base64 of Shard::to_bytes, 64 characters per line>
+//   -----END SCHRÖDINGER SHARD-----
+//
+// The headers are for people reading the block; when present they must agree
+// with the encoded shard.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+const BEGIN: &str = "-----BEGIN SCHRÖDINGER SHARD-----";
+const END: &str = "-----END SCHRÖDINGER SHARD-----";
+const LINE_WIDTH: usize = 64;
+
+pub(crate) fn encode(shard: &Shard) -> String {
+    let body = STANDARD.encode(shard.to_bytes());
+
+    let mut pem = format!("{}\nVersion: {}\nIndex: {}\n\n", BEGIN, shard.version, shard.index);
+    // Base64 output is ASCII, so byte chunks are whole characters
+    for line in body.as_bytes().chunks(LINE_WIDTH) {
+        pem.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        pem.push('\n');
+    }
+    pem.push_str(END);
+    pem.push('\n');
+    pem
+}
+
+pub(crate) fn decode(s: &str) -> Result<Shard, TimelockError> {
+    let invalid = |msg: &str| TimelockError::InvalidShardFormat(msg.to_string());
+
+    let mut lines = s.lines().map(str::trim).skip_while(|line| line.is_empty());
+    if lines.next() != Some(BEGIN) {
+        return Err(invalid("missing BEGIN SCHRÖDINGER SHARD line"));
+    }
+
+    let mut version = None;
+    let mut index = None;
+    let mut body = String::new();
+    let mut ended = false;
+    for line in lines.by_ref() {
+        if line == END {
+            ended = true;
+            break;
+        }
+        if let Some((name, value)) = line.split_once(": ") {
+            let value = value.trim().parse::<u8>()
+                .map_err(|_| TimelockError::InvalidShardFormat(format!("bad {} header: {}", name, value)))?;
+            match name {
+                "Version" => version = Some(value),
+                "Index" => index = Some(value),
+                _ => return Err(TimelockError::InvalidShardFormat(format!("unknown header: {}", name))),
+            }
+        } else {
+            body.push_str(line);
+        }
+    }
+    if !ended {
+        return Err(invalid("missing END SCHRÖDINGER SHARD line"));
+    }
+
+    let bytes = STANDARD.decode(&body)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("bad base64: {}", e)))?;
+    let shard = Shard::from_bytes(&bytes)?;
+    if version.is_some_and(|v| v != shard.version) || index.is_some_and(|i| i != shard.index) {
+        return Err(invalid("Version or Index header does not match the encoded shard"));
+    }
+    Ok(shard)
+}
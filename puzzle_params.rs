@@ -0,0 +1,14 @@
+// Public parameters of a timelock puzzle, enough for a third party to solve it
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleParams {
+    pub difficulty: u32,
+    // Sequential squarings per puzzle, 2^difficulty
+    pub iterations: u64,
+    // Big-endian hex of the RSA modulus n, if known
+    pub modulus_hex: Option<String>,
+    // Big-endian hex of the base a that is squared `iterations` times, if the
+    // parameters describe a single puzzle rather than a whole shard set
+    pub initial_value_hex: Option<String>,
+}
@@ -0,0 +1,55 @@
+// QR code images of shards for printed backups
+//
+// The QR code carries `Shard::to_bytes` in byte mode at error-correction level H,
+// so about 30% of the symbol can be damaged and still scan.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use image::{GrayImage, ImageFormat, Luma};
+use qrcode::{Color, EcLevel, QrCode};
+use std::io::Cursor;
+
+// Light modules around the symbol, as the QR specification requires
+const QUIET_ZONE_MODULES: u32 = 4;
+
+pub(crate) fn encode(shard: &Shard, scale: u32) -> Result<Vec<u8>, TimelockError> {
+    if scale == 0 {
+        return Err(TimelockError::InvalidConfiguration("QR code scale must be at least 1".to_string()));
+    }
+
+    let code = QrCode::with_error_correction_level(shard.to_bytes(), EcLevel::H)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("shard does not fit in a QR code: {}", e)))?;
+    let width = code.width() as u32;
+    let colors = code.to_colors();
+
+    let side = (width + 2 * QUIET_ZONE_MODULES) * scale;
+    let image = GrayImage::from_fn(side, side, |px, py| {
+        let (mx, my) = (px / scale, py / scale);
+        let inside = (QUIET_ZONE_MODULES..QUIET_ZONE_MODULES + width).contains(&mx)
+            && (QUIET_ZONE_MODULES..QUIET_ZONE_MODULES + width).contains(&my);
+        let dark = inside && colors[((my - QUIET_ZONE_MODULES) * width + mx - QUIET_ZONE_MODULES) as usize] == Color::Dark;
+        Luma([if dark { 0 } else { 255 }])
+    });
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("PNG encoding failed: {}", e)))?;
+    Ok(png)
+}
+
+pub(crate) fn decode(png: &[u8]) -> Result<Shard, TimelockError> {
+    let image = image::load_from_memory_with_format(png, ImageFormat::Png)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("not a readable PNG: {}", e)))?
+        .to_luma8();
+
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize, image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32)[0]);
+    let grid = prepared.detect_grids().into_iter().next()
+        .ok_or_else(|| TimelockError::InvalidShardFormat("no QR code found in image".to_string()))?;
+
+    let mut bytes = Vec::new();
+    grid.decode_to(&mut bytes)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("QR code could not be decoded: {}", e)))?;
+    Shard::from_bytes(&bytes)
+}
@@ -0,0 +1,150 @@
+// Limit on failed reconstructions, so an attacker holding some shards cannot
+// cheaply try thousands of forged ones to make up the threshold
+
+use crate::error::TimelockError;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Once `max_attempts` reconstructions have failed within `window`, further
+// attempts are refused until the oldest of those failures leaves the window.
+// Clones share their state, so one limiter can guard several configurations.
+#[derive(Debug, Clone)]
+pub struct ReconstructRateLimiter {
+    max_attempts: u32,
+    window: Duration,
+    state: Arc<Mutex<RateLimitState>>,
+}
+
+#[derive(Debug, Default)]
+pub struct RateLimitState {
+    // Times of recent failures, oldest first
+    failures: VecDeque<SystemTime>,
+    // File the failures are saved to after every change, if persistent
+    path: Option<PathBuf>,
+}
+
+impl ReconstructRateLimiter {
+    pub fn new(max_attempts: u32, window: Duration) -> Self {
+        ReconstructRateLimiter {
+            max_attempts,
+            window,
+            state: Arc::new(Mutex::new(RateLimitState::default())),
+        }
+    }
+
+    // As `new`, keeping failures in `path` so the limit survives restarts. The
+    // file holds one failure time per line, in milliseconds since the Unix epoch.
+    pub fn persistent(max_attempts: u32, window: Duration, path: impl Into<PathBuf>) -> Result<Self, TimelockError> {
+        let path = path.into();
+        let failures = match fs::read_to_string(&path) {
+            Ok(text) => text.lines()
+                .map(|line| line.trim().parse::<u64>()
+                    .map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+                    .map_err(|_| TimelockError::InvalidConfiguration(format!(
+                        "rate limit state {} is corrupted", path.display()))))
+                .collect::<Result<VecDeque<_>, _>>()?,
+            Err(e) if e.kind() == ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(ReconstructRateLimiter {
+            max_attempts,
+            window,
+            state: Arc::new(Mutex::new(RateLimitState { failures, path: Some(path) })),
+        })
+    }
+
+    // `Err(RateLimited)` if no further attempt is allowed yet
+    pub fn check(&self) -> Result<(), TimelockError> {
+        let mut state = self.lock();
+        let now = SystemTime::now();
+        self.expire(&mut state, now);
+        self.refusal(&state, now).map_or(Ok(()), Err)
+    }
+
+    // As `check`, but an allowed attempt is counted as a failure straight away,
+    // in the same step, so concurrent attempts cannot all slip under the limit.
+    // Pass the returned time to `release` once the attempt succeeds.
+    pub fn reserve(&self) -> Result<SystemTime, TimelockError> {
+        let mut state = self.lock();
+        let now = SystemTime::now();
+        self.expire(&mut state, now);
+        if let Some(refusal) = self.refusal(&state, now) {
+            return Err(refusal);
+        }
+        state.failures.push_back(now);
+        state.save()?;
+        Ok(now)
+    }
+
+    // Withdraw the failure `reserve` counted for an attempt that succeeded. If
+    // the change cannot be saved the limiter only stays stricter, so that is
+    // not an error.
+    pub fn release(&self, reserved: SystemTime) {
+        let mut state = self.lock();
+        if let Some(position) = state.failures.iter().position(|&failure| failure == reserved) {
+            state.failures.remove(position);
+            let _ = state.save();
+        }
+    }
+
+    pub fn record_failure(&self) -> Result<(), TimelockError> {
+        let mut state = self.lock();
+        let now = SystemTime::now();
+        self.expire(&mut state, now);
+        state.failures.push_back(now);
+        state.save()
+    }
+
+    // Failures still inside the window
+    pub fn recent_failures(&self) -> usize {
+        let mut state = self.lock();
+        self.expire(&mut state, SystemTime::now());
+        state.failures.len()
+    }
+
+    // `RateLimited` if the failures still in the window leave no attempt
+    fn refusal(&self, state: &RateLimitState, now: SystemTime) -> Option<TimelockError> {
+        if state.failures.len() < self.max_attempts as usize {
+            return None;
+        }
+        let retry_after = state.failures.front()
+            .map_or(self.window, |&oldest| (oldest + self.window).duration_since(now).unwrap_or_default());
+        Some(TimelockError::RateLimited { retry_after })
+    }
+
+    fn expire(&self, state: &mut RateLimitState, now: SystemTime) {
+        while state.failures.front()
+            .is_some_and(|&failure| now.duration_since(failure).unwrap_or_default() >= self.window)
+        {
+            state.failures.pop_front();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RateLimitState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl RateLimitState {
+    // Written to a sibling file first so an interruption never leaves torn state
+    fn save(&self) -> Result<(), TimelockError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text: String = self.failures.iter()
+            .map(|failure| {
+                let millis = failure.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+                format!("{}\n", millis)
+            })
+            .collect();
+        let partial = path.with_extension("partial");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+// Fetching shards that custodians host at HTTPS URLs
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+
+// GET `url`, sending `bearer_token` as an `Authorization: Bearer` header if
+// given. The body may hold a PEM-armored shard or a JSON one. Transport
+// failures and non-2xx responses are `Storage` errors.
+pub async fn fetch_shard(url: &str, bearer_token: Option<&str>) -> Result<Shard, TimelockError> {
+    let mut request = reqwest::Client::new().get(url);
+    if let Some(token) = bearer_token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request.send().await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| TimelockError::Storage(format!("{}: {}", url, e)))?;
+    let body = response.text().await
+        .map_err(|e| TimelockError::Storage(format!("{}: {}", url, e)))?;
+
+    let body = body.trim_start();
+    if body.starts_with('{') {
+        serde_json::from_str(body)
+            .map_err(|e| TimelockError::InvalidShardFormat(format!("{}: {}", url, e)))
+    } else {
+        Shard::from_pem(body)
+    }
+}
+
+// Fetch every URL concurrently, pairing each with the token at the same
+// position, and fail with the first error in URL order
+pub(crate) async fn fetch_shards(urls: &[&str], tokens: &[Option<&str>]) -> Result<Vec<Shard>, TimelockError> {
+    if urls.len() != tokens.len() {
+        return Err(TimelockError::InvalidConfiguration(format!(
+            "{} shard URLs were given with {} tokens", urls.len(), tokens.len())));
+    }
+
+    futures::future::join_all(urls.iter().zip(tokens).map(|(url, token)| fetch_shard(url, *token)))
+        .await
+        .into_iter()
+        .collect()
+}
@@ -0,0 +1,119 @@
+// Changing the threshold and shard count of a plain Shamir shard set (see
+// `ShamirSplit`) without ever assembling the key
+//
+// Any t old shares s_i at x_i determine the key as sum_i l_i * s_i, where l_i is
+// x_i's Lagrange coefficient at zero. Each of t old holders shares l_i * s_i
+// under a fresh polynomial of the new threshold, sending one sub-shard to each
+// new holder; a new holder's share is the sum of the sub-shards they receive.
+// The new shares then lie on sum_i l_i * g_i, whose constant term is the key.
+//
+// Timelocked shards must have their puzzles solved first: the share values are
+// locked inside them.
+
+use crate::error::TimelockError;
+use crate::field::{FiniteField, GF256};
+use crate::secret_splitter::{check_split_params, distinct_shares, plain_shard, share_bytes};
+use crate::shard::Shard;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReshareProtocol {
+    // x-coordinates of the old shards taking part, exactly the old threshold of them
+    old_indices: Vec<u8>,
+    new_threshold: usize,
+    new_total: usize,
+}
+
+impl ReshareProtocol {
+    pub fn new(old_indices: &[u8], new_threshold: usize, new_total: usize) -> Result<Self, TimelockError> {
+        check_split_params(new_total, new_threshold)?;
+        for (position, &x) in old_indices.iter().enumerate() {
+            if x == 0 {
+                return Err(TimelockError::CorruptedShard { index: position });
+            }
+            if old_indices[..position].contains(&x) {
+                return Err(TimelockError::DuplicateShard { index: x });
+            }
+        }
+        Ok(ReshareProtocol { old_indices: old_indices.to_vec(), new_threshold, new_total })
+    }
+
+    // Run by the holder of `shard`, one of the old shards taking part: one
+    // sub-shard per new holder, the i-th for the holder of new shard i + 1
+    pub fn sub_shards(&self, shard: &Shard, rng: &mut dyn RngCore) -> Result<Vec<Shard>, TimelockError> {
+        let shares = distinct_shares(std::slice::from_ref(shard))?;
+        let (x, share) = shares[0];
+        let position = self.old_indices.iter()
+            .position(|&old_x| old_x == x)
+            .ok_or_else(|| TimelockError::InvalidConfiguration(format!(
+                "shard {} is not taking part in this resharing", x)))?;
+        let xs: Vec<GF256> = self.old_indices.iter().map(|&x| GF256(x)).collect();
+        let basis = GF256::lagrange_at_zero(&xs, position);
+
+        let scaled: Zeroizing<Vec<u8>> = Zeroizing::new(share.iter()
+            .map(|&value| GF256::mul(basis, GF256(value)).0)
+            .collect());
+
+        Ok(share_bytes(&scaled, self.new_total, self.new_threshold, rng).iter()
+            .zip(1..=self.new_total as u8)
+            .map(|(values, new_x)| plain_shard(new_x, self.new_total, self.new_threshold, values))
+            .collect())
+    }
+
+    // Run by a new holder: their new shard from the sub-shards every old
+    // holder taking part sent them
+    pub fn combine_sub_shards(&self, sub_shards: &[Shard]) -> Result<Shard, TimelockError> {
+        if sub_shards.len() != self.old_indices.len() {
+            return Err(TimelockError::InsufficientShards {
+                provided: sub_shards.len(),
+                required: self.old_indices.len(),
+            });
+        }
+
+        let mut new_x = None;
+        let mut sum = Zeroizing::new(Vec::new());
+        for (index, sub_shard) in sub_shards.iter().enumerate() {
+            let (&x, share) = sub_shard.payload.split_first().ok_or(TimelockError::CorruptedShard { index })?;
+            if x == 0 || new_x.is_some_and(|new_x| new_x != x) || (index > 0 && share.len() != sum.len()) {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            new_x = Some(x);
+            sum.resize(share.len(), 0);
+            for (byte, &value) in sum.iter_mut().zip(share) {
+                *byte = GF256::add(GF256(*byte), GF256(value)).0;
+            }
+        }
+
+        let new_x = new_x.ok_or(TimelockError::InsufficientShards { provided: 0, required: 1 })?;
+        Ok(plain_shard(new_x, self.new_total, self.new_threshold, &sum))
+    }
+}
+
+// Run the whole protocol in one place, for when every old holder is present:
+// reshare the first threshold-many distinct `old_shards` into `new_total`
+// shards, any `new_threshold` of which recover the same key with `ShamirSplit`
+pub fn reshare(old_shards: &[Shard], new_threshold: usize, new_total: usize, rng: &mut impl RngCore)
+    -> Result<Vec<Shard>, TimelockError>
+{
+    let shares = distinct_shares(old_shards)?;
+    let required = (old_shards[0].threshold as usize).max(1);
+    if shares.len() < required {
+        return Err(TimelockError::InsufficientShards { provided: shares.len(), required });
+    }
+
+    let old_indices: Vec<u8> = shares[..required].iter().map(|&(x, _)| x).collect();
+    let protocol = ReshareProtocol::new(&old_indices, new_threshold, new_total)?;
+
+    // `distinct_shares` rejects repeats, so its shares line up with `old_shards`
+    let sub_shards = old_shards[..required].iter()
+        .map(|shard| protocol.sub_shards(shard, rng))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    (0..new_total)
+        .map(|j| {
+            let received: Vec<Shard> = sub_shards.iter().map(|from_holder| from_holder[j].clone()).collect();
+            protocol.combine_sub_shards(&received)
+        })
+        .collect()
+}
@@ -0,0 +1,6 @@
+// Marker for values that may only be compared in constant time
+//
+// `tests/constant_time.rs` rejects any `==` or `!=` on a line that mentions a
+// `SecretData` type, a shard MAC or a commitment. Compare them with
+// `subtle::ConstantTimeEq::ct_eq` instead.
+pub trait SecretData {}
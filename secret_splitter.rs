@@ -0,0 +1,193 @@
+// Interchangeable schemes for splitting a key into shards
+
+use crate::error::TimelockError;
+use crate::shard::{Shard, MAX_SHARDS, MIN_PAYLOAD_LEN};
+use crate::sss;
+use rand::RngCore;
+use std::collections::HashSet;
+use std::fmt;
+use zeroize::Zeroizing;
+
+// A secret sharing scheme. `LCS35` implements it with timelocked Shamir
+// shares; `XorSplit`, `ShamirSplit` and `BlakleySplitter` produce shards that
+// are not timelocked.
+pub trait SecretSplitter: fmt::Debug + Send + Sync {
+    // Split `key` into `num_shards` shards, any `threshold` of which recover it
+    fn split(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut dyn RngCore)
+        -> Result<Vec<Shard>, TimelockError>;
+
+    // Recover the key from shards produced by `split`
+    fn combine(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError>;
+
+    // Shortest payload `split` deals, for a one-byte key. `Shard::validate` only
+    // knows the shortest any scheme deals.
+    fn min_payload_len(&self) -> usize {
+        MIN_PAYLOAD_LEN
+    }
+}
+
+// Reject shard counts that do not fit the one-byte x-coordinate, and thresholds
+// outside 2..=num_shards, which `Shard::validate` would refuse to decode
+pub(crate) fn check_split_params(num_shards: usize, threshold: usize) -> Result<(), TimelockError> {
+    check_shard_count(num_shards)?;
+    if num_shards < 2 {
+        return Err(TimelockError::InvalidConfiguration(format!(
+            "number of shards must be between 2 and {}, got {}", MAX_SHARDS, num_shards)));
+    }
+    if threshold < 2 || threshold > num_shards {
+        return Err(TimelockError::InvalidConfiguration(format!(
+            "threshold must be between 2 and {}, got {}", num_shards, threshold)));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_shard_count(num_shards: usize) -> Result<(), TimelockError> {
+    if num_shards > MAX_SHARDS {
+        return Err(TimelockError::TooManyShards { requested: num_shards, max: MAX_SHARDS });
+    }
+    Ok(())
+}
+
+// n-of-n sharing: n - 1 random shares plus key XOR all of them. Every shard is
+// needed to recover the key, but splitting costs no field arithmetic at all.
+// Payload layout: [x, share...]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XorSplit;
+
+impl SecretSplitter for XorSplit {
+    fn split(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut dyn RngCore)
+        -> Result<Vec<Shard>, TimelockError>
+    {
+        check_split_params(num_shards, threshold)?;
+        if threshold != num_shards {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "XOR splitting needs all shards, so threshold must equal {}", num_shards)));
+        }
+
+        let mut last = Zeroizing::new(key.to_vec());
+        let mut shares = Vec::with_capacity(num_shards);
+        for _ in 1..num_shards {
+            let mut share = Zeroizing::new(vec![0u8; key.len()]);
+            rng.fill_bytes(&mut share);
+            for (byte, &mask) in last.iter_mut().zip(share.iter()) {
+                *byte ^= mask;
+            }
+            shares.push(share);
+        }
+        shares.push(last);
+
+        Ok(shares.iter()
+            .zip(1..=num_shards as u8)
+            .map(|(share, x)| plain_shard(x, num_shards, threshold, share))
+            .collect())
+    }
+
+    fn combine(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        let shares = distinct_shares(shards)?;
+        let required = shards[0].total as usize;
+        if shares.len() < required {
+            return Err(TimelockError::InsufficientShards { provided: shares.len(), required });
+        }
+
+        let mut key = vec![0u8; shares[0].1.len()];
+        for (_, share) in &shares {
+            for (byte, &value) in key.iter_mut().zip(*share) {
+                *byte ^= value;
+            }
+        }
+        Ok(key)
+    }
+}
+
+// Plain (not timelocked) Shamir sharing over GF(256), with one polynomial per
+// key byte as in `LCS35::shard`. Payload layout: [x, y_0, y_1, ...]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShamirSplit;
+
+impl SecretSplitter for ShamirSplit {
+    fn split(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut dyn RngCore)
+        -> Result<Vec<Shard>, TimelockError>
+    {
+        check_split_params(num_shards, threshold)?;
+
+        Ok(share_bytes(key, num_shards, threshold, rng).iter()
+            .zip(1..=num_shards as u8)
+            .map(|(values, x)| plain_shard(x, num_shards, threshold, values))
+            .collect())
+    }
+
+    fn combine(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        let shares = distinct_shares(shards)?;
+        let required = (shards[0].threshold as usize).max(1);
+        if shares.len() < required {
+            return Err(TimelockError::InsufficientShards { provided: shares.len(), required });
+        }
+
+        Ok(combine_bytes(&shares[..required]))
+    }
+}
+
+// Shamir-share every byte of `secret` with `sss::split_byte` among the
+// x-coordinates 1..=num_shards; the i-th list holds the values at x = i + 1
+pub(crate) fn share_bytes<R: RngCore + ?Sized>(secret: &[u8], num_shards: usize, threshold: usize, rng: &mut R)
+    -> Vec<Zeroizing<Vec<u8>>>
+{
+    let xs: Vec<u8> = (1..=num_shards as u8).collect();
+    let mut values: Vec<Zeroizing<Vec<u8>>> = xs.iter()
+        .map(|_| Zeroizing::new(Vec::with_capacity(secret.len())))
+        .collect();
+    let mut ys = Zeroizing::new(vec![0u8; num_shards]);
+    for &secret_byte in secret {
+        sss::split_byte(secret_byte, threshold as u8, &xs, &mut ys, rng);
+        for (values, &y) in values.iter_mut().zip(ys.iter()) {
+            values.push(y);
+        }
+    }
+    values
+}
+
+// Recover every byte with `sss::combine_byte` from (x, share) pairs with
+// distinct x-coordinates and shares of equal length
+pub(crate) fn combine_bytes<S: AsRef<[u8]>>(shares: &[(u8, S)]) -> Vec<u8> {
+    let len = shares.first().map_or(0, |(_, share)| share.as_ref().len());
+    let mut secret = Vec::with_capacity(len);
+    let mut points: Zeroizing<Vec<(u8, u8)>> = Zeroizing::new(Vec::with_capacity(shares.len()));
+    for byte_index in 0..len {
+        points.clear();
+        points.extend(shares.iter().map(|(x, share)| (*x, share.as_ref()[byte_index])));
+        secret.push(sss::combine_byte(&points));
+    }
+    secret
+}
+
+pub(crate) fn plain_shard(x: u8, num_shards: usize, threshold: usize, share: &[u8]) -> Shard {
+    let mut payload = Vec::with_capacity(1 + share.len());
+    payload.push(x);
+    payload.extend_from_slice(share);
+    Shard::new(x, num_shards as u8, threshold as u8, payload)
+}
+
+// Split [x, share...] payloads, rejecting an empty set, a zero or repeated
+// x-coordinate, and shares of differing lengths
+pub(crate) fn distinct_shares(shards: &[Shard]) -> Result<Vec<(u8, &[u8])>, TimelockError> {
+    if shards.is_empty() {
+        return Err(TimelockError::InsufficientShards { provided: 0, required: 1 });
+    }
+
+    let mut seen = HashSet::with_capacity(shards.len());
+    let mut shares = Vec::with_capacity(shards.len());
+    for (index, shard) in shards.iter().enumerate() {
+        let (&x, share) = shard.payload.split_first().ok_or(TimelockError::CorruptedShard { index })?;
+        if x == 0 {
+            return Err(TimelockError::CorruptedShard { index });
+        }
+        if !seen.insert(x) {
+            return Err(TimelockError::DuplicateShard { index: x });
+        }
+        if shares.first().is_some_and(|(_, first): &(u8, &[u8])| first.len() != share.len()) {
+            return Err(TimelockError::CorruptedShard { index });
+        }
+        shares.push((x, share));
+    }
+    Ok(shares)
+}
@@ -0,0 +1,360 @@
+// A single key shard together with the metadata needed to reconstruct it
+
+use crate::encrypted_shard::EncryptedShard;
+use crate::error::TimelockError;
+use crate::format;
+use crate::maturity::MaturityClock;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Version of the shard binary format given to new shards. Every earlier
+// version is still read; see `format.rs` for the layouts.
+pub const CURRENT_VERSION: u8 = 3;
+
+pub const MAC_LEN: usize = 32;
+
+// Smallest payload `Shard::validate` accepts: an x-coordinate and one share
+// byte, the least any splitter deals (see `SecretSplitter::min_payload_len`)
+pub const MIN_PAYLOAD_LEN: usize = 2;
+
+// Most shards one key can be split into: x-coordinates, like `Shard::index`,
+// are a single byte and 0 is never dealt. Sets larger than this would need a
+// `LargeShardIndex` format with u16 indices and sharing over GF(2^16) rather
+// than GF(256); none exists yet.
+pub const MAX_SHARDS: usize = u8::MAX as usize;
+
+// A maturity clock is stored as its u64 LE release timestamp after the MAC
+pub(crate) const MATURITY_LEN: usize = 8;
+
+// Payload bytes are wiped when a shard is dropped. Deserialized shards are
+// checked with `validate`.
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "UncheckedShard"))]
+pub struct Shard {
+    // Position of this shard within its set, starting at 1
+    pub index: u8,
+    // Number of shards the key was split into
+    pub total: u8,
+    // Number of shards required to reconstruct the key
+    pub threshold: u8,
+    // Binary format version
+    pub version: u8,
+    // Timelocked share data; the first byte is the Shamir x-coordinate
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub payload: Vec<u8>,
+    // CRC32 of the payload, catching bit rot before any puzzle is solved
+    pub checksum: u32,
+    // HMAC-SHA256 over index || payload, keyed by a key derived from the master key
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_with::As::<Option<serde_with::hex::Hex>>"))]
+    pub mac: Option<[u8; MAC_LEN]>,
+    // Calendar time before which the shard must not be released
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maturity_clock: Option<MaturityClock>,
+}
+
+impl Shard {
+    // Shard in the current format with its checksum computed and no MAC
+    pub fn new(index: u8, total: u8, threshold: u8, payload: Vec<u8>) -> Self {
+        Shard {
+            index,
+            total,
+            threshold,
+            version: CURRENT_VERSION,
+            checksum: crc32fast::hash(&payload),
+            payload,
+            mac: None,
+            maturity_clock: None,
+        }
+    }
+
+    // Hold this shard back until `clock` matures; see `verify_shard_set`
+    pub fn with_maturity_clock(mut self, clock: MaturityClock) -> Self {
+        self.maturity_clock = Some(clock);
+        self
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, TimelockError> {
+        Self::from_bytes(&hex::decode(s)?)
+    }
+
+    // Crockford base32 of `to_bytes`: digits and uppercase letters without
+    // I, L, O or U, for reading aloud or copying by hand
+    pub fn to_base32(&self) -> String {
+        base32::encode(base32::Alphabet::Crockford, &self.to_bytes())
+    }
+
+    // Inverse of `to_base32`. Case is ignored, and spaces and hyphens may be
+    // inserted anywhere to group the characters.
+    pub fn from_base32(s: &str) -> Result<Self, TimelockError> {
+        let cleaned: String = s.chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let bytes = base32::decode(base32::Alphabet::Crockford, &cleaned)
+            .ok_or_else(|| TimelockError::InvalidShardFormat("not valid Crockford base32".to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    // One emoji per byte of `to_bytes`; see `encoding::emoji` for the alphabet
+    pub fn to_emoji(&self) -> String {
+        crate::encoding::emoji::encode(self)
+    }
+
+    pub fn from_emoji(s: &str) -> Result<Self, TimelockError> {
+        crate::encoding::emoji::decode(s)
+    }
+
+    // ASCII-armored block with human-readable Version and Index headers
+    pub fn to_pem(&self) -> String {
+        crate::pem::encode(self)
+    }
+
+    pub fn from_pem(s: &str) -> Result<Self, TimelockError> {
+        crate::pem::decode(s)
+    }
+
+    // Encrypt this shard under a 32-byte transport key with AES-256-GCM
+    pub fn encrypt(&self, key: &[u8; 32]) -> EncryptedShard {
+        EncryptedShard::seal(self, key)
+    }
+
+    // Compact binary form for database columns and constrained links
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        crate::cbor::encode(self)
+    }
+
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(b: &[u8]) -> Result<Self, TimelockError> {
+        crate::cbor::decode(b)
+    }
+
+    // PNG image of a QR code carrying this shard, `scale` pixels per module
+    #[cfg(feature = "qr")]
+    pub fn to_qr_code(&self, scale: u32) -> Result<Vec<u8>, TimelockError> {
+        crate::qr::encode(self, scale)
+    }
+
+    // Inverse of `to_qr_code`
+    #[cfg(feature = "qr")]
+    pub fn from_qr_code_bytes(png: &[u8]) -> Result<Self, TimelockError> {
+        crate::qr::decode(png)
+    }
+
+    // NDEF-sized records for NFC tags; see `nfc::reassemble_from_nfc` for the reverse
+    pub fn split_for_nfc(&self, max_record_size: usize) -> Vec<Vec<u8>> {
+        crate::nfc::split_for_nfc(self, max_record_size)
+    }
+
+    // Mnemonic words for paper backups; see `mnemonic::decode_shard` for the reverse
+    pub fn to_mnemonic(&self) -> Vec<String> {
+        crate::mnemonic::encode_shard(self)
+    }
+
+    // Layout of the shard's `version`; see `format.rs`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        format::encode(self)
+    }
+
+    // Decoded by the layout its version byte names, then checked with `validate`
+    pub fn from_bytes(b: &[u8]) -> Result<Self, TimelockError> {
+        let shard = format::decode(b)?;
+        shard.validate()?;
+        Ok(shard)
+    }
+
+    // Check that the metadata is consistent: index >= 1, 2 <= threshold <= total,
+    // a payload of at least `MIN_PAYLOAD_LEN` bytes and a version no newer than
+    // `CURRENT_VERSION`. The index may exceed `total`, as it does for shards
+    // issued later by `TimelockKeySharding::add_shard`. Every decoder runs this
+    // before returning a shard.
+    pub fn validate(&self) -> Result<(), TimelockError> {
+        if self.index < 1 {
+            return Err(TimelockError::InvalidShardFormat("shard index 0 is never dealt".to_string()));
+        }
+        if self.threshold < 2 || self.threshold > self.total {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "threshold {} is outside 2..={}", self.threshold, self.total)));
+        }
+        if self.payload.len() < MIN_PAYLOAD_LEN {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "payload is {} bytes, expected at least {}", self.payload.len(), MIN_PAYLOAD_LEN)));
+        }
+        if self.version > CURRENT_VERSION {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "shard version {} is newer than the supported version {}", self.version, CURRENT_VERSION)));
+        }
+        Ok(())
+    }
+
+    // Whether the payload still matches the checksum recorded when the shard was made
+    pub fn verify_checksum(&self) -> bool {
+        crc32fast::hash(&self.payload) == self.checksum
+    }
+
+    // Derive the shard authentication key from the master key being sharded
+    pub fn derive_mac_key(master_key: &[u8]) -> [u8; MAC_LEN] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(master_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(b"schrodinger shard mac key");
+        mac.finalize().into_bytes().into()
+    }
+
+    // HMAC-SHA256(mac_key, index || payload)
+    pub fn compute_mac(&self, mac_key: &[u8]) -> [u8; MAC_LEN] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(mac_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(&[self.index]);
+        mac.update(&self.payload);
+        mac.finalize().into_bytes().into()
+    }
+
+    // Constant-time check of the stored MAC; shards without a MAC never verify
+    pub fn verify_mac(&self, mac_key: &[u8]) -> bool {
+        match &self.mac {
+            Some(mac) => self.compute_mac(mac_key).as_slice().ct_eq(mac.as_slice()).into(),
+            None => false,
+        }
+    }
+}
+
+// Written out rather than derived so the MAC is compared in constant time, as
+// in `verify_mac`
+impl PartialEq for Shard {
+    fn eq(&self, other: &Self) -> bool {
+        let macs_match = match (&self.mac, &other.mac) {
+            (Some(ours), Some(theirs)) => bool::from(ours.as_slice().ct_eq(theirs.as_slice())),
+            (None, None) => true,
+            _ => false,
+        };
+        self.index == other.index
+            && self.total == other.total
+            && self.threshold == other.threshold
+            && self.version == other.version
+            && self.payload == other.payload
+            && self.checksum == other.checksum
+            && self.maturity_clock == other.maturity_clock
+            && macs_match
+    }
+}
+
+impl Eq for Shard {}
+
+// Serde's view of a shard before `validate` has accepted it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedShard {
+    index: u8,
+    total: u8,
+    threshold: u8,
+    version: u8,
+    #[serde(with = "serde_with::As::<serde_with::hex::Hex>")]
+    payload: Vec<u8>,
+    checksum: u32,
+    #[serde(default, with = "serde_with::As::<Option<serde_with::hex::Hex>>")]
+    mac: Option<[u8; MAC_LEN]>,
+    #[serde(default)]
+    maturity_clock: Option<MaturityClock>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedShard> for Shard {
+    type Error = TimelockError;
+
+    fn try_from(unchecked: UncheckedShard) -> Result<Self, Self::Error> {
+        let shard = Shard {
+            index: unchecked.index,
+            total: unchecked.total,
+            threshold: unchecked.threshold,
+            version: unchecked.version,
+            payload: unchecked.payload,
+            checksum: unchecked.checksum,
+            mac: unchecked.mac,
+            maturity_clock: unchecked.maturity_clock,
+        };
+        shard.validate()?;
+        Ok(shard)
+    }
+}
+
+// PEM armor, so shards print and parse as text
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_pem())
+    }
+}
+
+impl FromStr for Shard {
+    type Err = TimelockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Shard::from_pem(s)
+    }
+}
+
+// Summary of a shard set that passed `verify_shard_set`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardSetInfo {
+    pub total: u8,
+    pub threshold: u8,
+    pub version: u8,
+    // Every shard is well formed and there are enough of them to reconstruct
+    pub all_valid: bool,
+}
+
+// Cheap pre-flight check of a shard set before any puzzle is solved. Shards that
+// fail their checksum, disagree on version, total or threshold, or repeat an
+// x-coordinate are an error, as is a maturity clock that has not yet matured;
+// individually malformed shards or too few shards clear `all_valid`.
+pub fn verify_shard_set(shards: &[Shard]) -> Result<ShardSetInfo, TimelockError> {
+    let first = shards.first()
+        .ok_or(TimelockError::InsufficientShards { provided: 0, required: 1 })?;
+
+    let mut seen = HashSet::with_capacity(shards.len());
+    let mut all_valid = shards.len() >= first.threshold as usize;
+    for (index, shard) in shards.iter().enumerate() {
+        if !shard.verify_checksum() {
+            return Err(TimelockError::CorruptedShard { index });
+        }
+        if shard.version != first.version {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "shard {} has version {}, expected {}", index, shard.version, first.version)));
+        }
+        if shard.total != first.total || shard.threshold != first.threshold {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "shard {} belongs to a {}-of-{} set, expected {}-of-{}",
+                index, shard.threshold, shard.total, first.threshold, first.total)));
+        }
+        if let Some(&x) = shard.payload.first() {
+            if !seen.insert(x) {
+                return Err(TimelockError::DuplicateShard { index: x });
+            }
+        }
+        if let Some(clock) = shard.maturity_clock.filter(|clock| !clock.is_mature()) {
+            return Err(TimelockError::NotYetMature { release_at: clock.release_timestamp() });
+        }
+
+        // Shards issued later by `TimelockKeySharding::add_shard` sit past `total`
+        all_valid &= (1..=CURRENT_VERSION).contains(&shard.version)
+            && shard.index >= 1
+            && (1..=shard.total).contains(&shard.threshold)
+            && shard.payload.first().is_some_and(|&x| x != 0);
+    }
+
+    Ok(ShardSetInfo {
+        total: first.total,
+        threshold: first.threshold,
+        version: first.version,
+        all_valid,
+    })
+}
@@ -0,0 +1,63 @@
+// Shards as CSV rows for custodians who keep them in a spreadsheet
+//
+//   index,total,threshold,version,payload_hex
+//   1,5,3,3,02a4...
+//
+// Only these five columns are kept: the checksum is recomputed from the payload
+// on reading, and any MAC or maturity clock is dropped.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use std::io::{BufRead, Write};
+
+pub const CSV_HEADER: &str = "index,total,threshold,version,payload_hex";
+
+impl Shard {
+    pub fn to_csv_row(&self) -> String {
+        format!("{},{},{},{},{}", self.index, self.total, self.threshold, self.version, hex::encode(&self.payload))
+    }
+
+    // Spreadsheets may quote fields or pad them with spaces; both are ignored
+    pub fn from_csv_row(row: &str) -> Result<Self, TimelockError> {
+        let fields: Vec<&str> = row.trim().split(',').map(|field| field.trim().trim_matches('"').trim()).collect();
+        let [index, total, threshold, version, payload] = fields.as_slice() else {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "expected 5 CSV fields ({}), found {}", CSV_HEADER, fields.len())));
+        };
+
+        let byte = |name: &str, value: &str| value.parse::<u8>().map_err(|_| TimelockError::InvalidShardFormat(format!(
+            "CSV field `{}` must be a number from 0 to 255, found {:?}", name, value)));
+        let mut shard = Shard::new(byte("index", index)?, byte("total", total)?, byte("threshold", threshold)?,
+                                   hex::decode(payload)?);
+        shard.version = byte("version", version)?;
+        shard.validate()?;
+        Ok(shard)
+    }
+}
+
+// `CSV_HEADER` followed by one row per shard
+pub fn write_shard_csv(shards: &[Shard], writer: &mut impl Write) -> Result<(), TimelockError> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+    for shard in shards {
+        writeln!(writer, "{}", shard.to_csv_row())?;
+    }
+    Ok(())
+}
+
+// Inverse of `write_shard_csv`. The header row is optional and blank lines are
+// skipped; a malformed row fails with its 1-based line number.
+pub fn read_shard_csv(reader: impl BufRead) -> Result<Vec<Shard>, TimelockError> {
+    let mut shards = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let row = line.trim();
+        if row.is_empty() || (number == 0 && row.trim_start_matches('\u{feff}').eq_ignore_ascii_case(CSV_HEADER)) {
+            continue;
+        }
+        let shard = Shard::from_csv_row(row.trim_start_matches('\u{feff}')).map_err(|e| {
+            TimelockError::InvalidShardFormat(format!("line {}: {}", number + 1, e))
+        })?;
+        shards.push(shard);
+    }
+    Ok(shards)
+}
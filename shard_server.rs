@@ -0,0 +1,120 @@
+// HTTP service through which a custodian hosts shards for `fetch_shard`
+//
+//   GET  /shards/:index   the stored shard, PEM-armored
+//   POST /shards          store the PEM-armored shard in the request body
+//
+// Every request must carry an `Authorization: Bearer <token>` header naming one
+// of the configured tokens.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use crate::storage::StorageBackend;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::Router;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use subtle::{Choice, ConstantTimeEq};
+
+// Comma-separated bearer tokens accepted by `serve`
+pub const TOKENS_ENV: &str = "SCHRODINGER_SHARD_TOKENS";
+
+type Rejection = (StatusCode, String);
+
+#[derive(Clone)]
+struct ServerState<B> {
+    backend: B,
+    tokens: Arc<Vec<String>>,
+}
+
+// As `serve_with_tokens`, accepting the tokens listed in `SCHRODINGER_SHARD_TOKENS`.
+// With the variable unset every request is refused.
+pub fn serve<B>(backend: B, bind_addr: &str) -> impl Future<Output = ()>
+where
+    B: StorageBackend + Clone + Send + Sync + 'static,
+{
+    let tokens = std::env::var(TOKENS_ENV).unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(String::from)
+        .collect();
+    serve_with_tokens(backend, bind_addr, tokens)
+}
+
+// Serve `backend` on `bind_addr` until the process exits, accepting any of
+// `tokens`. Panics if the address cannot be bound.
+pub fn serve_with_tokens<B>(backend: B, bind_addr: &str, tokens: Vec<String>) -> impl Future<Output = ()>
+where
+    B: StorageBackend + Clone + Send + Sync + 'static,
+{
+    let bind_addr = bind_addr.to_string();
+    let state = ServerState { backend, tokens: Arc::new(tokens) };
+    async move {
+        let app = Router::new()
+            .route("/shards/:index", get(get_shard::<B>))
+            .route("/shards", post(post_shard::<B>))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind(&bind_addr).await
+            .unwrap_or_else(|e| panic!("cannot bind shard server to {}: {}", bind_addr, e));
+        axum::serve(listener, app).await
+            .unwrap_or_else(|e| panic!("shard server on {} failed: {}", bind_addr, e));
+    }
+}
+
+async fn get_shard<B>(State(state): State<ServerState<B>>, headers: HeaderMap, Path(index): Path<u8>)
+    -> Result<String, Rejection>
+where
+    B: StorageBackend + Clone + Send + Sync + 'static,
+{
+    authorize(&state.tokens, &headers)?;
+    let backend = state.backend.clone();
+    let shard = tokio::task::spawn_blocking(move || backend.load_shard(index)).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(reject)?;
+    Ok(shard.to_pem())
+}
+
+async fn post_shard<B>(State(state): State<ServerState<B>>, headers: HeaderMap, body: String)
+    -> Result<StatusCode, Rejection>
+where
+    B: StorageBackend + Clone + Send + Sync + 'static,
+{
+    authorize(&state.tokens, &headers)?;
+    let shard = Shard::from_pem(&body).map_err(reject)?;
+    let backend = state.backend.clone();
+    tokio::task::spawn_blocking(move || backend.store_shard(&shard)).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(reject)?;
+    Ok(StatusCode::CREATED)
+}
+
+// Every configured token is compared in constant time, so response timing
+// reveals neither which token matched nor how much of one did
+fn authorize(tokens: &[String], headers: &HeaderMap) -> Result<(), Rejection> {
+    let presented = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "missing bearer token".to_string()))?;
+
+    let matched = tokens.iter()
+        .fold(Choice::from(0), |matched, token| matched | token.as_bytes().ct_eq(presented.as_bytes()));
+    if bool::from(matched) {
+        Ok(())
+    } else {
+        Err((StatusCode::UNAUTHORIZED, "unknown bearer token".to_string()))
+    }
+}
+
+fn reject(e: TimelockError) -> Rejection {
+    let status = match &e {
+        TimelockError::Io(io_error) if io_error.kind() == io::ErrorKind::NotFound => StatusCode::NOT_FOUND,
+        TimelockError::InvalidShardFormat(_) | TimelockError::CorruptedShard { .. }
+            | TimelockError::HexDecodeError(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, e.to_string())
+}
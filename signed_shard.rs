@@ -0,0 +1,43 @@
+// Ed25519 signatures over shards
+//
+// Unlike the HMAC, which needs a key derived from the secret, a signature can be
+// checked by any custodian holding the issuer's public key.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+
+pub const SIGNATURE_LEN: usize = 64;
+
+// Domain separation so a shard signature cannot be replayed as any other message
+const SIGNATURE_CONTEXT: &[u8] = b"schrodinger shard signature";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedShard {
+    pub shard: Shard,
+    // Ed25519 signature over the context string and `Shard::to_bytes`
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub signature: [u8; SIGNATURE_LEN],
+}
+
+pub fn sign_shard(shard: &Shard, signing_key: &SigningKey) -> SignedShard {
+    SignedShard {
+        shard: shard.clone(),
+        signature: signing_key.sign(&signed_message(shard)).to_bytes(),
+    }
+}
+
+// Return the shard only if the signature verifies under `verifying_key`
+pub fn verify_signed_shard(signed: &SignedShard, verifying_key: &VerifyingKey) -> Result<Shard, TimelockError> {
+    let signature = Signature::from_bytes(&signed.signature);
+    verifying_key.verify_strict(&signed_message(&signed.shard), &signature)
+        .map_err(|_| TimelockError::InvalidSignature)?;
+    Ok(signed.shard.clone())
+}
+
+fn signed_message(shard: &Shard) -> Vec<u8> {
+    let mut message = SIGNATURE_CONTEXT.to_vec();
+    message.extend_from_slice(&shard.to_bytes());
+    message
+}
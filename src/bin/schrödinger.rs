@@ -0,0 +1,388 @@
+// Command-line front end for timelock key sharding
+//
+//   schrödinger shard --key-file <path> --shards <n> --threshold <k> --output-dir <dir> [--mnemonic]
+//   schrödinger generate-key --length <bytes> --shards <n> --threshold <k> --output-dir <dir>
+//                             [--passphrase-derive] [--no-display]
+//   schrödinger reconstruct --shard-files <paths...> --output <file> [--progress]
+//   schrödinger verify --shard-files <paths...>
+//   schrödinger inspect <path> [--json] [--commitment <hex>]
+//   schrödinger bench [--target-years <years>]
+//   schrödinger migrate --input-dir <dir> --output-dir <dir>
+//
+// Shards are stored one file per custodian, named `shard_001.pem` onwards or,
+// with `--mnemonic`, `shard_001.txt` onwards holding mnemonic words; JSON shard
+// files are read as well. Shard and key files are readable only by their owner
+// on Unix. Any error exits with code 1.
+
+use clap::{Parser, Subcommand};
+use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use zeroize::{Zeroize, Zeroizing};
+use timelock_key_sharing::entropy::calculate_entropy;
+use timelock_key_sharing::{
+    default_benchmark_path, migrate_v1_to_v2, verify_shard_commitment, verify_shard_set, write_owner_only, Argon2Params,
+    BenchmarkResult, CancellationToken, Difficulty, MnemonicShard, Shard, ShardCommitment, TimelockError,
+    TimelockKeySharding, TimelockKeyShardingBuilder, DEFAULT_MODULUS_BITS, LCS35,
+};
+
+// 2^20 squarings per shard: seconds on current hardware
+const DEFAULT_DIFFICULTY: u32 = 20;
+
+// Squarings between progress bar updates; each update's rate sample, and so
+// the time remaining shown, covers this many squarings
+const PROGRESS_SAMPLE_SQUARINGS: u64 = 1000;
+
+const BENCHMARK_DURATION: Duration = Duration::from_secs(5);
+
+// Rows of the `bench` table, in years
+const BENCHMARK_TARGETS: [(&str, f64); 7] = [
+    ("1 day", 1.0 / 365.25),
+    ("1 month", 1.0 / 12.0),
+    ("1 year", 1.0),
+    ("5 years", 5.0),
+    ("10 years", 10.0),
+    ("35 years", 35.0),
+    ("100 years", 100.0),
+];
+
+#[derive(Parser)]
+#[command(name = "schrödinger", version, about = "Split keys into timelocked shards and recover them")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Split a key file into one shard file per custodian")]
+    Shard {
+        #[arg(long)]
+        key_file: PathBuf,
+        #[arg(long)]
+        shards: usize,
+        #[arg(long)]
+        threshold: usize,
+        #[arg(long)]
+        output_dir: PathBuf,
+        #[arg(long, default_value_t = DEFAULT_DIFFICULTY,
+              help = "Each shard's puzzle takes 2^difficulty sequential squarings to open")]
+        difficulty: u32,
+        #[arg(long, help = "Write each shard as mnemonic words for paper backups instead of JSON")]
+        mnemonic: bool,
+    },
+    #[command(about = "Generate a random key and split it into shard files; the key itself is never written")]
+    GenerateKey {
+        #[arg(long, default_value_t = 32, help = "Key length in bytes")]
+        length: usize,
+        #[arg(long)]
+        shards: usize,
+        #[arg(long)]
+        threshold: usize,
+        #[arg(long)]
+        output_dir: PathBuf,
+        #[arg(long, default_value_t = DEFAULT_DIFFICULTY,
+              help = "Each shard's puzzle takes 2^difficulty sequential squarings to open")]
+        difficulty: u32,
+        #[arg(long, help = "Derive a 32-byte key from a passphrase with Argon2id instead of generating one")]
+        passphrase_derive: bool,
+        #[arg(long, help = "Do not print the key")]
+        no_display: bool,
+        #[arg(long, help = "Write each shard as mnemonic words for paper backups instead of JSON")]
+        mnemonic: bool,
+    },
+    #[command(about = "Solve the shards' puzzles and write the recovered key")]
+    Reconstruct {
+        #[arg(long, num_args = 1.., required = true)]
+        shard_files: Vec<PathBuf>,
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, default_value_t = DEFAULT_DIFFICULTY,
+              help = "Must match the difficulty the shards were created with")]
+        difficulty: u32,
+        #[arg(long, help = "Show a progress bar with the estimated time remaining")]
+        progress: bool,
+    },
+    #[command(about = "Check that the shards form a consistent set and report their entropy")]
+    Verify {
+        #[arg(long, num_args = 1.., required = true)]
+        shard_files: Vec<PathBuf>,
+    },
+    #[command(about = "Print a single shard's metadata without solving its puzzle")]
+    Inspect {
+        shard_file: PathBuf,
+        #[arg(long, help = "Print a JSON object instead of plain text")]
+        json: bool,
+        #[arg(long, help = "Also check the shard against the hex commitment published for it")]
+        commitment: Option<String>,
+    },
+    #[command(about = "Measure this machine's squaring speed and recommend a difficulty")]
+    Bench {
+        #[arg(long, default_value_t = 35.0, help = "How long the recommended difficulty should hold, in years")]
+        target_years: f64,
+    },
+    #[command(about = "Upgrade version 1 PEM shard files to version 2, adding checksums")]
+    Migrate {
+        #[arg(long)]
+        input_dir: PathBuf,
+        #[arg(long)]
+        output_dir: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Shard { key_file, shards, threshold, output_dir, difficulty, mnemonic } => {
+            let sharding = TimelockKeyShardingBuilder::default()
+                .difficulty(difficulty)
+                .threshold(threshold)
+                .total_shards(shards)
+                .build()?;
+            let key = Zeroizing::new(fs::read(&key_file)?);
+            write_shards(&sharding.shard_bytes(&key, shards)?, &output_dir, mnemonic)?;
+        }
+        Command::GenerateKey { length, shards, threshold, output_dir, difficulty, passphrase_derive, no_display,
+                               mnemonic } => {
+            let sharding = TimelockKeyShardingBuilder::default()
+                .difficulty(difficulty)
+                .threshold(threshold)
+                .total_shards(shards)
+                .build()?;
+
+            let (issued, key) = if passphrase_derive {
+                if length != 32 {
+                    return Err(format!("--passphrase-derive always yields a 32-byte key, not {}", length).into());
+                }
+                let passphrase = Zeroizing::new(rpassword::prompt_password("passphrase: ")?);
+                let confirmation = Zeroizing::new(rpassword::prompt_password("repeat passphrase: ")?);
+                if *passphrase != *confirmation {
+                    return Err("passphrases do not match".into());
+                }
+                let mut salt = [0u8; 32];
+                getrandom::getrandom(&mut salt).map_err(|e| format!("cannot generate salt: {}", e))?;
+                let (issued, mut derived) = sharding.shard_passphrase(&passphrase, &salt, shards, Argon2Params::default())?;
+                let key = Zeroizing::new(derived.to_vec());
+                derived.zeroize();
+                // Not secret, but needed with the passphrase to derive the key again
+                println!("salt: {}", hex::encode(salt));
+                (issued, key)
+            } else {
+                let mut key = Zeroizing::new(vec![0u8; length]);
+                getrandom::getrandom(&mut key).map_err(|e| format!("cannot generate key: {}", e))?;
+                (sharding.shard_bytes(&key, shards)?, key)
+            };
+
+            write_shards(&issued, &output_dir, mnemonic)?;
+            if !no_display {
+                // Shown this once; `key` is zeroized when it drops at the end of this arm
+                let encoded = Zeroizing::new(hex::encode(&*key));
+                println!("key: {}", *encoded);
+            }
+        }
+        Command::Reconstruct { shard_files, output, difficulty, progress } => {
+            let shards = read_shards(&shard_files)?;
+            // Refuse inconsistent or not yet mature shards before any squaring
+            let threshold = verify_shard_set(&shards)?.threshold as usize;
+            let key = if progress {
+                Zeroizing::new(reconstruct_with_progress_bar(&shards, threshold, difficulty)?)
+            } else {
+                let sharding = TimelockKeySharding::new(difficulty, threshold);
+                Zeroizing::new(sharding.reconstruct_bytes(&shards)?)
+            };
+            write_owner_only(&output, &key)?;
+            println!("wrote {} key bytes to {}", key.len(), output.display());
+        }
+        Command::Verify { shard_files } => {
+            let shards = read_shards(&shard_files)?;
+            let info = verify_shard_set(&shards)?;
+            println!("{} shards of a {}-of-{} set, version {}", shards.len(), info.threshold, info.total, info.version);
+            println!("all shards valid: {}", info.all_valid);
+
+            let report = TimelockKeySharding::new(DEFAULT_DIFFICULTY, info.threshold as usize).check_shard_entropy(&shards);
+            for (metrics, path) in report.per_shard.iter().zip(&shard_files) {
+                println!("  {}: {:.2} bits/byte, entropy {}, Borel {}, runs {}, chi-squared {}, spectral {}",
+                         path.display(), metrics.shannon_entropy, verdict(metrics.passed_entropy),
+                         verdict(metrics.passed_borel), verdict(metrics.passed_runs),
+                         verdict(metrics.chi_squared_passed), verdict(metrics.spectral.passed));
+            }
+            println!("entropy check: {}", verdict(report.passed));
+        }
+        Command::Inspect { shard_file, json, commitment } => {
+            let shard = read_shard(&shard_file)?;
+            let entropy = calculate_entropy(&shard.payload);
+            let checksum_valid = shard.verify_checksum();
+            let commitment_valid = commitment.as_deref()
+                .map(ShardCommitment::from_hex)
+                .transpose()?
+                .map(|commitment| verify_shard_commitment(&shard, &commitment));
+            if json {
+                let summary = serde_json::json!({
+                    "index": shard.index,
+                    "total": shard.total,
+                    "threshold": shard.threshold,
+                    "version": shard.version,
+                    "payload_len": shard.payload.len(),
+                    "shannon_entropy": entropy,
+                    "checksum_valid": checksum_valid,
+                    "has_mac": shard.mac.is_some(),
+                    "commitment_valid": commitment_valid,
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!("index:           {}", shard.index);
+                println!("total shards:    {}", shard.total);
+                println!("threshold:       {}", shard.threshold);
+                println!("version:         {}", shard.version);
+                println!("payload length:  {} bytes", shard.payload.len());
+                println!("shannon entropy: {:.4} bits/byte", entropy);
+                println!("checksum:        {}", if checksum_valid { "valid" } else { "INVALID" });
+                println!("mac:             {}", if shard.mac.is_some() { "present" } else { "absent" });
+                if let Some(valid) = commitment_valid {
+                    println!("commitment:      {}", if valid { "matches" } else { "MISMATCH" });
+                }
+            }
+        }
+        Command::Bench { target_years } => {
+            if !(target_years.is_finite() && target_years > 0.0) {
+                return Err(format!("--target-years must be positive, not {}", target_years).into());
+            }
+            println!("squaring for {} seconds...", BENCHMARK_DURATION.as_secs());
+            let rate = LCS35::benchmark(BENCHMARK_DURATION);
+            println!("{} squarings/s with a {}-bit modulus", rate, DEFAULT_MODULUS_BITS);
+
+            let recommended = Difficulty::from_years(target_years, rate);
+            println!("recommended difficulty for {} years: {} ({:.1} years per puzzle)",
+                     target_years, recommended.get(), recommended.to_years(rate));
+
+            println!();
+            println!("{:<12} {:>10} {:>16}", "target", "difficulty", "years per puzzle");
+            for (label, years) in BENCHMARK_TARGETS {
+                let difficulty = Difficulty::from_years(years, rate);
+                println!("{:<12} {:>10} {:>16.3}", label, difficulty.get(), difficulty.to_years(rate));
+            }
+
+            let path = default_benchmark_path().ok_or("no home directory to save the benchmark in")?;
+            BenchmarkResult::new(rate, BENCHMARK_DURATION, DEFAULT_MODULUS_BITS).save(&path)?;
+            println!();
+            println!("saved benchmark to {}", path.display());
+        }
+        Command::Migrate { input_dir, output_dir } => {
+            let mut pem_files = fs::read_dir(&input_dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            pem_files.retain(|path| path.extension().is_some_and(|ext| ext == "pem"));
+            pem_files.sort();
+
+            fs::create_dir_all(&output_dir)?;
+            for path in &pem_files {
+                let shard = read_shard(path)?;
+                let from_version = shard.version;
+                let migrated = migrate_v1_to_v2(shard);
+                let output = output_dir.join(path.file_name().expect("read_dir entries have file names"));
+                write_owner_only(&output, migrated.to_pem().as_bytes())?;
+                println!("{} (version {}) -> {} (version {})",
+                         path.display(), from_version, output.display(), migrated.version);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Named and written like `TimelockKeySharding::split_key_to_files`, removing
+// the files already written if one fails
+fn write_shards(shards: &[Shard], output_dir: &Path, mnemonic: bool) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(output_dir)?;
+    let mut written = Vec::with_capacity(shards.len());
+    for shard in shards {
+        let (path, contents) = if mnemonic {
+            (output_dir.join(format!("shard_{:03}.txt", shard.index)), MnemonicShard::from_shard(shard).to_string())
+        } else {
+            (output_dir.join(format!("shard_{:03}.pem", shard.index)), shard.to_pem())
+        };
+        if let Err(e) = write_owner_only(&path, contents.as_bytes()) {
+            for path in written.iter().chain([&path]) {
+                let _ = fs::remove_file(path);
+            }
+            return Err(format!("{}: {}", path.display(), e).into());
+        }
+        written.push(path);
+    }
+    for path in &written {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// Solve the puzzles directly rather than through `TimelockKeySharding`, so the
+// squaring loop can report to the bar
+fn reconstruct_with_progress_bar(shards: &[Shard], threshold: usize, difficulty: u32)
+    -> Result<Vec<u8>, TimelockError>
+{
+    let bar = ProgressBar::new(0);
+    bar.set_style(ProgressStyle::with_template("{bar:40} {pos}/{len} squarings, {msg}")
+        .expect("progress template is valid"));
+    bar.set_message("estimating time remaining");
+
+    // Completed count and time of the previous update
+    let last_sample = Mutex::new((0u64, Instant::now()));
+    let puzzle = LCS35::for_unlock(difficulty).with_progress_interval(PROGRESS_SAMPLE_SQUARINGS);
+    let result = puzzle.unlock_with_progress(shards, threshold, |completed, total| {
+        bar.set_length(total);
+        bar.set_position(completed);
+
+        let now = Instant::now();
+        let mut last = last_sample.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let elapsed = now.duration_since(last.1).as_secs_f64();
+        let rate = completed.saturating_sub(last.0) as f64 / elapsed;
+        if rate.is_finite() && rate > 0.0 {
+            let remaining = Duration::from_secs_f64(total.saturating_sub(completed) as f64 / rate);
+            bar.set_message(format!("{} remaining at {:.0} squarings/s", HumanDuration(remaining), rate));
+        }
+        *last = (completed, now);
+    }, &CancellationToken::new());
+
+    bar.finish_and_clear();
+    result
+}
+
+fn read_shards(paths: &[PathBuf]) -> Result<Vec<Shard>, Box<dyn Error>> {
+    let shards = paths.iter()
+        .map(|path| read_shard(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    if shards.is_empty() {
+        return Err(TimelockError::InsufficientShards { provided: 0, required: 1 }.into());
+    }
+    Ok(shards)
+}
+
+// JSON, PEM or mnemonic words, told apart by their first characters
+fn read_shard(path: &Path) -> Result<Shard, Box<dyn Error>> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let trimmed = text.trim_start();
+    let shard = if trimmed.starts_with('{') {
+        serde_json::from_str(trimmed).map_err(|e| e.to_string())
+    } else if trimmed.starts_with("-----BEGIN") {
+        Shard::from_pem(trimmed).map_err(|e| e.to_string())
+    } else {
+        trimmed.parse::<MnemonicShard>().and_then(|words| words.to_shard()).map_err(|e| e.to_string())
+    };
+    Ok(shard.map_err(|e| format!("{}: {}", path.display(), e))?)
+}
+
+fn verdict(passed: bool) -> &'static str {
+    if passed { "PASSED" } else { "FAILED" }
+}
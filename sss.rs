@@ -0,0 +1,176 @@
+// Plain Shamir secret sharing over GF(256) without the standard library
+//
+// Everything here lives on the stack with fixed capacities, so it runs in
+// embedded firmware under `no_std`. Shares are not timelocked; use `LCS35`
+// for that on a host with `std`.
+//
+// `split_byte` and `combine_byte` are the per-byte core that `split` and
+// `combine` are built on. The `std` splitters (`ShamirSplit`, `LCS35`'s
+// reconstruction, weighted, hierarchical and resharing) use them as well, so
+// every key byte is shared the same way.
+
+use crate::field::{self, GF256};
+use core::fmt;
+use rand::RngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+// Longest secret `split` accepts, in bytes
+pub const MAX_SECRET_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SssError {
+    // The secret is longer than `MAX_SECRET_LEN`
+    SecretTooLong { len: usize, max: usize },
+    // The share count is below 2 or above the output capacity
+    InvalidShareCount { requested: u8, max: usize },
+    // The threshold is outside 2..=num_shares
+    InvalidThreshold { threshold: u8, num_shares: u8 },
+    // `combine` was given no shares
+    NoShares,
+    // The share at `index` has the x-coordinate 0, which is never dealt
+    ZeroCoordinate { index: usize },
+    // More than one share uses the x-coordinate `x`
+    DuplicateShare { x: u8 },
+    // The share at `index` holds a different number of bytes from the first
+    LengthMismatch { index: usize, len: usize, expected: usize },
+}
+
+impl fmt::Display for SssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SssError::SecretTooLong { len, max } => {
+                write!(f, "secret is {} bytes, at most {} are supported", len, max)
+            }
+            SssError::InvalidShareCount { requested, max } => {
+                write!(f, "number of shares must be between 2 and {}, got {}", max, requested)
+            }
+            SssError::InvalidThreshold { threshold, num_shares } => {
+                write!(f, "threshold must be between 2 and {}, got {}", num_shares, threshold)
+            }
+            SssError::NoShares => write!(f, "no shares supplied"),
+            SssError::ZeroCoordinate { index } => write!(f, "share {} has x-coordinate 0", index),
+            SssError::DuplicateShare { x } => write!(f, "x-coordinate {} was supplied more than once", x),
+            SssError::LengthMismatch { index, len, expected } => {
+                write!(f, "share {} is {} bytes, expected {}", index, len, expected)
+            }
+        }
+    }
+}
+
+// One share: the x-coordinate and one polynomial value per secret byte.
+// The values are wiped when the share is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub x: u8,
+    pub y: heapless::Vec<u8, MAX_SECRET_LEN>,
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.y.as_mut_slice().zeroize();
+    }
+}
+
+// Split `secret` into `num_shares` shares, any `threshold` of which recover it.
+// `N` is the capacity of the returned list and must be at least `num_shares`.
+pub fn split<const N: usize, R: RngCore + ?Sized>(secret: &[u8], num_shares: u8, threshold: u8, rng: &mut R)
+    -> Result<heapless::Vec<Share, N>, SssError>
+{
+    if secret.len() > MAX_SECRET_LEN {
+        return Err(SssError::SecretTooLong { len: secret.len(), max: MAX_SECRET_LEN });
+    }
+    if num_shares < 2 || num_shares as usize > N {
+        return Err(SssError::InvalidShareCount { requested: num_shares, max: N });
+    }
+    if threshold < 2 || threshold > num_shares {
+        return Err(SssError::InvalidThreshold { threshold, num_shares });
+    }
+
+    let mut xs = [0u8; u8::MAX as usize];
+    let xs = &mut xs[..num_shares as usize];
+    let mut shares = heapless::Vec::new();
+    for (x, slot) in (1..=num_shares).zip(xs.iter_mut()) {
+        *slot = x;
+        // Capacity was checked against `num_shares` above
+        let _ = shares.push(Share { x, y: heapless::Vec::new() });
+    }
+
+    let mut ys = Zeroizing::new([0u8; u8::MAX as usize]);
+    let ys = &mut ys[..num_shares as usize];
+    for &secret_byte in secret {
+        split_byte(secret_byte, threshold, xs, ys, rng);
+        for (share, &y) in shares.iter_mut().zip(ys.iter()) {
+            // Secret length was checked against the share capacity above
+            let _ = share.y.push(y);
+        }
+    }
+    Ok(shares)
+}
+
+// Recover the secret from shares produced by `split`. Every supplied share is
+// interpolated, so passing fewer than the threshold silently yields garbage.
+pub fn combine(shares: &[Share]) -> Result<heapless::Vec<u8, MAX_SECRET_LEN>, SssError> {
+    let first = shares.first().ok_or(SssError::NoShares)?;
+
+    let mut seen = [false; 256];
+    for (index, share) in shares.iter().enumerate() {
+        if share.x == 0 {
+            return Err(SssError::ZeroCoordinate { index });
+        }
+        if seen[share.x as usize] {
+            return Err(SssError::DuplicateShare { x: share.x });
+        }
+        seen[share.x as usize] = true;
+        if share.y.len() != first.y.len() {
+            return Err(SssError::LengthMismatch { index, len: share.y.len(), expected: first.y.len() });
+        }
+    }
+
+    // Distinct non-zero x-coordinates mean at most 255 points
+    let mut points = Zeroizing::new([(0u8, 0u8); u8::MAX as usize]);
+    let points = &mut points[..shares.len()];
+    let mut secret = heapless::Vec::new();
+    for byte_index in 0..first.y.len() {
+        for (point, share) in points.iter_mut().zip(shares) {
+            *point = (share.x, share.y[byte_index]);
+        }
+        // Share lengths are bounded by the secret capacity
+        let _ = secret.push(combine_byte(points));
+    }
+    Ok(secret)
+}
+
+// Deal one secret byte: `ys[i]` becomes the value at `xs[i]` of a fresh random
+// polynomial of degree `threshold - 1` whose constant term is `secret_byte`.
+// Draws `threshold - 1` bytes from `rng`. Panics unless 1 <= threshold and `ys`
+// is as long as `xs`.
+pub fn split_byte<R: RngCore + ?Sized>(secret_byte: u8, threshold: u8, xs: &[u8], ys: &mut [u8], rng: &mut R) {
+    assert!(threshold >= 1, "a polynomial has at least one coefficient");
+    assert_eq!(xs.len(), ys.len(), "one value per x-coordinate");
+
+    let mut random = Zeroizing::new([0u8; u8::MAX as usize]);
+    let random = &mut random[..threshold as usize - 1];
+    rng.fill_bytes(random);
+
+    let mut polynomial = Zeroizing::new([GF256(0); u8::MAX as usize]);
+    let polynomial = &mut polynomial[..threshold as usize];
+    polynomial[0] = GF256(secret_byte);
+    for (coefficient, &byte) in polynomial[1..].iter_mut().zip(random.iter()) {
+        *coefficient = GF256(byte);
+    }
+    for (y, &x) in ys.iter_mut().zip(xs) {
+        *y = field::eval_poly(polynomial, GF256(x)).0;
+    }
+}
+
+// The secret byte behind `points`, one (x, y) pair from each of at least
+// `threshold` shares. Panics on no points or a repeated x-coordinate, which
+// callers reject first.
+pub fn combine_byte(points: &[(u8, u8)]) -> u8 {
+    let mut field_points = Zeroizing::new([(GF256(0), GF256(0)); u8::MAX as usize]);
+    let field_points = &mut field_points[..points.len()];
+    for (field_point, &(x, y)) in field_points.iter_mut().zip(points) {
+        *field_point = (GF256(x), GF256(y));
+    }
+    field::interpolate_at_zero(field_points).0
+}
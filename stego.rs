@@ -0,0 +1,67 @@
+// Hiding a shard in an ordinary PNG image
+//
+// Each pixel's red channel carries one bit in its least significant bit, in
+// row-major order: first the shard length (u32 LE), then `Shard::to_bytes`, each
+// byte least significant bit first. This hides the shard from casual inspection
+// only; it does not resist statistical steganalysis and does not survive lossy
+// re-encoding, so store the image as the PNG returned here.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+use image::{ImageFormat, RgbaImage};
+use std::io::Cursor;
+
+const LENGTH_PREFIX_LEN: usize = 4;
+
+pub fn embed_in_png(shard: &Shard, cover_image: &[u8]) -> Result<Vec<u8>, TimelockError> {
+    let mut image = load_rgba(cover_image)?;
+
+    let shard_bytes = shard.to_bytes();
+    let mut message = (shard_bytes.len() as u32).to_le_bytes().to_vec();
+    message.extend_from_slice(&shard_bytes);
+
+    let capacity = image.pixels().len();
+    if message.len() * 8 > capacity {
+        return Err(TimelockError::InvalidConfiguration(format!(
+            "cover image has {} pixels, but the shard needs {}", capacity, message.len() * 8)));
+    }
+
+    let bits = message.iter().flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1));
+    for (pixel, bit) in image.pixels_mut().zip(bits) {
+        pixel[0] = (pixel[0] & !1) | bit;
+    }
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("PNG encoding failed: {}", e)))?;
+    Ok(png)
+}
+
+pub fn extract_from_png(stego_image: &[u8]) -> Result<Shard, TimelockError> {
+    let image = load_rgba(stego_image)?;
+    let mut bits = image.pixels().map(|pixel| pixel[0] & 1);
+    let mut next_byte = || -> Option<u8> {
+        (0..8).try_fold(0u8, |byte, bit| Some(byte | bits.next()? << bit))
+    };
+    let truncated = || TimelockError::InvalidShardFormat("image is too small to hold the embedded shard".to_string());
+
+    let mut prefix = [0u8; LENGTH_PREFIX_LEN];
+    for byte in &mut prefix {
+        *byte = next_byte().ok_or_else(truncated)?;
+    }
+    let len = u32::from_le_bytes(prefix) as usize;
+    if (LENGTH_PREFIX_LEN + len).saturating_mul(8) > image.pixels().len() {
+        return Err(truncated());
+    }
+
+    let shard_bytes = (0..len)
+        .map(|_| next_byte().ok_or_else(truncated))
+        .collect::<Result<Vec<u8>, _>>()?;
+    Shard::from_bytes(&shard_bytes)
+}
+
+fn load_rgba(png: &[u8]) -> Result<RgbaImage, TimelockError> {
+    Ok(image::load_from_memory_with_format(png, ImageFormat::Png)
+        .map_err(|e| TimelockError::InvalidShardFormat(format!("not a readable PNG: {}", e)))?
+        .to_rgba8())
+}
@@ -0,0 +1,234 @@
+// Persistence of shards between generation and reconstruction
+
+use crate::error::TimelockError;
+#[cfg(feature = "sqlite")]
+use crate::maturity::MaturityClock;
+use crate::shard::Shard;
+#[cfg(feature = "serde")]
+use std::{fs, path::PathBuf};
+
+pub trait StorageBackend {
+    fn store_shard(&self, shard: &Shard) -> Result<(), TimelockError>;
+    fn load_shard(&self, index: u8) -> Result<Shard, TimelockError>;
+    // Indices of every stored shard, in ascending order
+    fn list_shards(&self) -> Result<Vec<u8>, TimelockError>;
+}
+
+// One JSON file per shard, named `shard_{index:03}.json`, under `root`
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct FileSystemBackend {
+    root: PathBuf,
+}
+
+#[cfg(feature = "serde")]
+impl FileSystemBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileSystemBackend { root: root.into() }
+    }
+
+    pub fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+
+    fn shard_path(&self, index: u8) -> PathBuf {
+        self.root.join(format!("shard_{:03}.json", index))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl StorageBackend for FileSystemBackend {
+    // Written to a sibling file first so an interruption never leaves a torn shard
+    fn store_shard(&self, shard: &Shard) -> Result<(), TimelockError> {
+        fs::create_dir_all(&self.root)?;
+        let path = self.shard_path(shard.index);
+        let partial = path.with_extension("partial");
+        let json = serde_json::to_vec_pretty(shard)
+            .map_err(|e| TimelockError::InvalidShardFormat(e.to_string()))?;
+        fs::write(&partial, json)?;
+        fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn load_shard(&self, index: u8) -> Result<Shard, TimelockError> {
+        let path = self.shard_path(index);
+        let shard: Shard = serde_json::from_slice(&fs::read(&path)?)
+            .map_err(|e| TimelockError::InvalidShardFormat(format!("{}: {}", path.display(), e)))?;
+        if shard.index != index {
+            return Err(TimelockError::InvalidShardFormat(format!(
+                "{} holds shard {}", path.display(), shard.index)));
+        }
+        Ok(shard)
+    }
+
+    fn list_shards(&self) -> Result<Vec<u8>, TimelockError> {
+        let mut indices = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let name = entry?.file_name();
+            let index = name.to_str()
+                .and_then(|name| name.strip_prefix("shard_")?.strip_suffix(".json"))
+                .filter(|digits| digits.len() == 3)
+                .and_then(|digits| digits.parse().ok());
+            if let Some(index) = index {
+                indices.push(index);
+            }
+        }
+        indices.sort_unstable();
+        Ok(indices)
+    }
+}
+
+// All shards in one SQLite database, in a `shards` table keyed by shard index
+#[cfg(feature = "sqlite")]
+pub struct SqliteBackend {
+    conn: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteBackend {
+    // Open or create the database at `path`, creating the `shards` table if absent
+    pub fn open(path: &std::path::Path) -> Result<Self, TimelockError> {
+        let conn = rusqlite::Connection::open(path).map_err(sqlite_error)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS shards (
+                \"index\" INTEGER PRIMARY KEY,
+                version INTEGER NOT NULL,
+                total INTEGER NOT NULL,
+                threshold INTEGER NOT NULL,
+                payload BLOB NOT NULL,
+                checksum INTEGER NOT NULL,
+                mac BLOB,
+                created_at INTEGER NOT NULL,
+                release_at INTEGER
+            )",
+        ).map_err(sqlite_error)?;
+
+        // Databases created before shards carried checksums lack the column;
+        // add it and fill in the CRC32 of each stored payload
+        if !has_column(&conn, "checksum")? {
+            let tx = conn.unchecked_transaction().map_err(sqlite_error)?;
+            tx.execute_batch("ALTER TABLE shards ADD COLUMN checksum INTEGER NOT NULL DEFAULT 0")
+                .map_err(sqlite_error)?;
+            let payloads = {
+                let mut statement = tx.prepare("SELECT \"index\", payload FROM shards").map_err(sqlite_error)?;
+                let rows = statement.query_map([], |row| Ok((row.get::<_, u8>(0)?, row.get::<_, Vec<u8>>(1)?)))
+                    .map_err(sqlite_error)?;
+                rows.collect::<Result<Vec<_>, _>>().map_err(sqlite_error)?
+            };
+            for (index, payload) in payloads {
+                tx.execute("UPDATE shards SET checksum = ?1 WHERE \"index\" = ?2",
+                           rusqlite::params![crc32fast::hash(&payload), index])
+                    .map_err(sqlite_error)?;
+            }
+            tx.commit().map_err(sqlite_error)?;
+        }
+        // Databases created before maturity clocks existed lack the column
+        if !has_column(&conn, "release_at")? {
+            conn.execute_batch("ALTER TABLE shards ADD COLUMN release_at INTEGER").map_err(sqlite_error)?;
+        }
+        Ok(SqliteBackend { conn })
+    }
+
+    // Every stored shard in index order, read as one consistent snapshot
+    pub fn export_all(&self) -> Result<Vec<Shard>, TimelockError> {
+        let tx = self.conn.unchecked_transaction().map_err(sqlite_error)?;
+        let shards = {
+            let mut statement = tx.prepare(
+                "SELECT \"index\", version, total, threshold, payload, checksum, mac, release_at FROM shards ORDER BY \"index\"",
+            ).map_err(sqlite_error)?;
+            let rows = statement.query_map([], row_to_columns).map_err(sqlite_error)?;
+            rows.map(|row| columns_to_shard(row.map_err(sqlite_error)?))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        tx.commit().map_err(sqlite_error)?;
+        Ok(shards)
+    }
+
+    // Store several shards atomically: either all of them are written or none are
+    pub fn store_all(&self, shards: &[Shard]) -> Result<(), TimelockError> {
+        let tx = self.conn.unchecked_transaction().map_err(sqlite_error)?;
+        for shard in shards {
+            insert_shard(&tx, shard)?;
+        }
+        tx.commit().map_err(sqlite_error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl StorageBackend for SqliteBackend {
+    fn store_shard(&self, shard: &Shard) -> Result<(), TimelockError> {
+        insert_shard(&self.conn, shard)
+    }
+
+    fn load_shard(&self, index: u8) -> Result<Shard, TimelockError> {
+        use rusqlite::OptionalExtension;
+
+        let columns = self.conn.query_row(
+            "SELECT \"index\", version, total, threshold, payload, checksum, mac, release_at FROM shards WHERE \"index\" = ?1",
+            [index],
+            row_to_columns,
+        ).optional().map_err(sqlite_error)?;
+        columns_to_shard(columns.ok_or_else(|| TimelockError::Storage(format!("no shard with index {}", index)))?)
+    }
+
+    fn list_shards(&self) -> Result<Vec<u8>, TimelockError> {
+        let mut statement = self.conn.prepare("SELECT \"index\" FROM shards ORDER BY \"index\"")
+            .map_err(sqlite_error)?;
+        let rows = statement.query_map([], |row| row.get(0)).map_err(sqlite_error)?;
+        rows.collect::<Result<Vec<u8>, _>>().map_err(sqlite_error)
+    }
+}
+
+// (index, version, total, threshold, payload, checksum, mac, release_at) as stored
+#[cfg(feature = "sqlite")]
+type ShardColumns = (u8, u8, u8, u8, Vec<u8>, u32, Option<Vec<u8>>, Option<i64>);
+
+#[cfg(feature = "sqlite")]
+fn row_to_columns(row: &rusqlite::Row<'_>) -> rusqlite::Result<ShardColumns> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?))
+}
+
+#[cfg(feature = "sqlite")]
+fn columns_to_shard((index, version, total, threshold, payload, checksum, mac, release_at): ShardColumns)
+    -> Result<Shard, TimelockError>
+{
+    let mac = mac.map(|mac| {
+        <[u8; crate::shard::MAC_LEN]>::try_from(mac.as_slice()).map_err(|_| TimelockError::InvalidShardFormat(format!(
+            "stored MAC of shard {} is {} bytes, expected {}", index, mac.len(), crate::shard::MAC_LEN)))
+    }).transpose()?;
+    let maturity_clock = release_at.map(|timestamp| MaturityClock::new(timestamp as u64));
+    let shard = Shard { index, total, threshold, version, payload, checksum, mac, maturity_clock };
+    shard.validate()?;
+    Ok(shard)
+}
+
+#[cfg(feature = "sqlite")]
+fn insert_shard(conn: &rusqlite::Connection, shard: &Shard) -> Result<(), TimelockError> {
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64);
+    conn.execute(
+        "INSERT OR REPLACE INTO shards (\"index\", version, total, threshold, payload, checksum, mac, created_at, release_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            shard.index, shard.version, shard.total, shard.threshold,
+            shard.payload, shard.checksum, shard.mac.as_ref().map(|mac| mac.as_slice()), created_at,
+            shard.maturity_clock.map(|clock| clock.release_timestamp() as i64),
+        ],
+    ).map_err(sqlite_error)?;
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+fn has_column(conn: &rusqlite::Connection, name: &str) -> Result<bool, TimelockError> {
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('shards') WHERE name = ?1",
+        [name],
+        |row| row.get(0),
+    ).map_err(sqlite_error)
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_error(e: rusqlite::Error) -> TimelockError {
+    TimelockError::Storage(e.to_string())
+}
@@ -0,0 +1,40 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{verify_shard_set, Shard, TimelockError, TimelockKeySharding};
+
+const KEY: &[u8] = b"a custodian joins later";
+
+#[test]
+fn an_added_shard_reconstructs_the_key_with_dealt_ones() {
+    let sharding = TimelockKeySharding::new(2, 3);
+    let mut rng = ChaChaRng::from_seed([12u8; 32]);
+    let (dealt, coefficients) = sharding.shard_with_coefficients(KEY, 5, &mut rng).unwrap();
+
+    let added = sharding.add_shard(&dealt, 6, &coefficients).unwrap();
+    assert_eq!((added.index, added.total, added.threshold), (6, 5, 3));
+
+    // The added shard survives storage like any other
+    let stored = Shard::from_bytes(&added.to_bytes()).unwrap();
+    assert_eq!(Shard::from_pem(&added.to_pem()).unwrap(), stored);
+
+    let mixed = [dealt[0].clone(), dealt[4].clone(), stored];
+    let info = verify_shard_set(&mixed).unwrap();
+    assert!(info.all_valid);
+    assert_eq!(sharding.reconstruct_bytes(&mixed).unwrap(), KEY);
+}
+
+#[test]
+fn added_shards_must_not_reuse_or_mismatch_the_dealt_set() {
+    let sharding = TimelockKeySharding::new(2, 3);
+    let mut rng = ChaChaRng::from_seed([13u8; 32]);
+    let (dealt, coefficients) = sharding.shard_with_coefficients(KEY, 5, &mut rng).unwrap();
+
+    let result = sharding.add_shard(&dealt, 5, &coefficients);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+
+    let result = sharding.add_shard(&dealt, 6, &coefficients[1..]);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+
+    let result = sharding.add_shard(&[], 6, &coefficients);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { .. })), "{:?}", result);
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+use timelock_key_sharing::{AuditEvent, AuditLog, FileAuditLog, TimelockError, TimelockKeySharding};
+
+#[derive(Debug, Default)]
+struct MemoryAuditLog {
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl AuditLog for MemoryAuditLog {
+    fn record(&self, event: AuditEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[test]
+fn issuance_and_reconstruction_are_recorded() {
+    let log = Arc::new(MemoryAuditLog::default());
+    let sharding = TimelockKeySharding::new(4, 2).with_audit_log(log.clone());
+
+    let shards = sharding.shard_bytes(b"audited sharded key", 3).unwrap();
+    assert_eq!(sharding.reconstruct_bytes(&shards[1..]).unwrap(), b"audited sharded key");
+    let result = sharding.reconstruct_bytes(&shards[..1]);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { provided: 1, required: 2 })), "{:?}", result);
+
+    let events = log.events.lock().unwrap();
+    let created: Vec<u8> = events.iter()
+        .filter_map(|event| match event {
+            AuditEvent::ShardCreated { index, .. } => Some(*index),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(created, [1, 2, 3]);
+    let attempts: Vec<(usize, bool)> = events.iter()
+        .filter_map(|event| match event {
+            AuditEvent::ReconstructionAttempted { num_shards, success, .. } => Some((*num_shards, *success)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(attempts, [(2, true), (1, false)]);
+}
+
+#[test]
+fn file_audit_log_appends_one_json_line_per_event() {
+    let path = std::env::temp_dir().join(format!("schrodinger-audit-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+    for index in 1..=2 {
+        FileAuditLog::open(&path).unwrap().record(AuditEvent::ShardCreated { index, timestamp: 1_700_000_000 });
+    }
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), concat!(
+        "{\"event\":\"ShardCreated\",\"index\":1,\"timestamp\":1700000000}\n",
+        "{\"event\":\"ShardCreated\",\"index\":2,\"timestamp\":1700000000}\n",
+    ));
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,30 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{BlakleySplitter, SecretSplitter, TimelockError, TimelockKeySharding};
+
+#[test]
+fn any_threshold_of_hyperplanes_recovers_the_key() {
+    let key = b"blakley hyperplanes meet here";
+    let splitter = BlakleySplitter::default();
+    let shards = splitter.split(key, 5, 3, &mut ChaChaRng::from_seed([61; 32])).unwrap();
+
+    assert_eq!(splitter.combine(&shards[..3]).unwrap(), key);
+    assert_eq!(splitter.combine(&[shards[4].clone(), shards[0].clone(), shards[2].clone()]).unwrap(), key);
+
+    let sharding = TimelockKeySharding::new(4, 2).with_splitter(Box::new(BlakleySplitter::new(65_537)));
+    let shards = sharding.shard_bytes(key, 3).unwrap();
+    assert_eq!(sharding.reconstruct_bytes(&shards[1..]).unwrap(), key);
+}
+
+#[test]
+fn mismatched_fields_and_short_sets_are_rejected() {
+    let mut rng = ChaChaRng::from_seed([62; 32]);
+    let result = BlakleySplitter::new(65_536).split(b"key", 3, 2, &mut rng);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+
+    let shards = BlakleySplitter::new(65_537).split(b"key", 3, 2, &mut rng).unwrap();
+    let result = BlakleySplitter::default().combine(&shards);
+    assert!(matches!(result, Err(TimelockError::CorruptedShard { index: 0 })), "{:?}", result);
+    let result = BlakleySplitter::new(65_537).combine(&shards[..1]);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { provided: 1, required: 2 })), "{:?}", result);
+}
@@ -0,0 +1,46 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{ThresholdProof, TimelockCapsule, TimelockError, TimelockKeySharding, THRESHOLD_SALT_LEN};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+fn custodians(count: usize, rng: &mut ChaChaRng) -> Vec<StaticSecret> {
+    (0..count).map(|_| StaticSecret::random_from_rng(&mut *rng)).collect()
+}
+
+#[test]
+fn custodians_open_their_shards_and_reconstruct_the_key() {
+    let mut rng = ChaChaRng::from_seed([91; 32]);
+    let secrets = custodians(3, &mut rng);
+    let public_keys: Vec<PublicKey> = secrets.iter().map(PublicKey::from).collect();
+    let config = TimelockKeySharding::new(4, 2);
+    let key = b"sealed in a timelock capsule";
+
+    let (proof, salt) = ThresholdProof::generate(2, &mut rng);
+    let capsule = TimelockCapsule::seal(key, &config, &public_keys, "quarterly rotation")
+        .unwrap()
+        .with_creator_id("vault-ops")
+        .with_threshold_proof(proof);
+    assert_eq!(capsule.encrypted_shards.len(), 3);
+    assert!(capsule.verify_threshold(&salt));
+
+    let shards: Vec<_> = secrets[1..].iter().map(|secret| capsule.open_shard(secret).unwrap()).collect();
+    assert_eq!(shards.iter().map(|shard| shard.index).collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(config.reconstruct_bytes(&shards).unwrap(), key);
+}
+
+#[test]
+fn strangers_and_undersized_recipient_lists_are_rejected() {
+    let mut rng = ChaChaRng::from_seed([92; 32]);
+    let secrets = custodians(3, &mut rng);
+    let public_keys: Vec<PublicKey> = secrets.iter().map(PublicKey::from).collect();
+    let config = TimelockKeySharding::new(4, 3);
+    let key = b"sealed in a timelock capsule";
+
+    let capsule = TimelockCapsule::seal(key, &config, &public_keys, "").unwrap();
+    let stranger = StaticSecret::random_from_rng(&mut rng);
+    assert!(matches!(capsule.open_shard(&stranger), Err(TimelockError::DecryptionFailed)));
+    assert!(!capsule.verify_threshold(&[0; THRESHOLD_SALT_LEN]));
+
+    let result = TimelockCapsule::seal(key, &config, &public_keys[..2], "");
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result.map(|_| ()));
+}
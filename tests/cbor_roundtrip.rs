@@ -0,0 +1,84 @@
+#![cfg(all(feature = "cbor", feature = "serde"))]
+
+use ciborium::value::Value;
+use timelock_key_sharing::{Shard, TimelockError};
+
+mod common;
+use common::shard;
+
+#[test]
+fn shard_cbor_round_trip() {
+    let (_, mut shards) = shard(b"cbor round trip key", 3, 2, [2u8; 32]);
+    shards[0].mac = Some(shards[0].compute_mac(&[7u8; 32]));
+
+    for shard in shards {
+        assert_eq!(Shard::from_cbor(&shard.to_cbor()).unwrap(), shard);
+    }
+}
+
+// `shard`'s CBOR map with `edit` applied to its fields
+fn edited_cbor(shard: &Shard, edit: impl FnOnce(&mut Vec<(Value, Value)>)) -> Vec<u8> {
+    let Ok(Value::Map(mut fields)) = ciborium::de::from_reader(shard.to_cbor().as_slice()) else {
+        panic!("a shard encodes as a CBOR map");
+    };
+    edit(&mut fields);
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&Value::Map(fields), &mut bytes).unwrap();
+    bytes
+}
+
+fn set_field(fields: &mut [(Value, Value)], name: &str, value: Value) {
+    let field = fields.iter_mut().find(|(key, _)| key.as_text() == Some(name)).unwrap();
+    field.1 = value;
+}
+
+#[test]
+fn malformed_cbor_maps_are_rejected() {
+    let (_, shards) = shard(b"cbor rejection key", 3, 2, [4u8; 32]);
+    let malformed = [
+        shards[0].to_cbor()[..10].to_vec(),
+        edited_cbor(&shards[0], |fields| fields.retain(|(key, _)| key.as_text() != Some("payload"))),
+        edited_cbor(&shards[0], |fields| set_field(fields, "index", Value::from(256))),
+        edited_cbor(&shards[0], |fields| set_field(fields, "payload", Value::from("not bytes"))),
+        edited_cbor(&shards[0], |fields| set_field(fields, "mac", Value::Bytes(vec![0; 5]))),
+        edited_cbor(&shards[0], |fields| set_field(fields, "threshold", Value::from(9))),
+    ];
+    for (case, bytes) in malformed.iter().enumerate() {
+        let result = Shard::from_cbor(bytes);
+        assert!(matches!(result, Err(TimelockError::InvalidShardFormat(_))), "case {}: {:?}", case, result);
+    }
+
+    let mut array = Vec::new();
+    ciborium::ser::into_writer(&Value::Array(vec![Value::from(1)]), &mut array).unwrap();
+    assert!(matches!(Shard::from_cbor(&array), Err(TimelockError::InvalidShardFormat(_))));
+}
+
+#[test]
+fn cbor_is_smaller_than_hex_json() {
+    let shard = Shard {
+        index: 1,
+        total: 3,
+        threshold: 2,
+        version: 1,
+        payload: vec![0xab; 33],
+        checksum: crc32fast::hash(&[0xab; 33]),
+        mac: None,
+        maturity_clock: None,
+    };
+
+    let cbor = shard.to_cbor().len();
+    let json = serde_json::to_vec(&shard).unwrap().len();
+    assert!(cbor * 10 <= json * 7, "CBOR {} bytes, JSON {} bytes", cbor, json);
+}
+
+#[cfg(feature = "bundle")]
+#[test]
+fn bundle_round_trip() {
+    use timelock_key_sharing::{pack_shards, unpack_shards};
+
+    let (_, shards) = shard(b"bundle round trip key", 4, 2, [3u8; 32]);
+
+    let bundle = pack_shards(&shards).unwrap();
+    assert_eq!(&bundle[..4], b"SCHR");
+    assert_eq!(unpack_shards(&bundle).unwrap(), shards);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::shard;
+use std::fs;
+use timelock_key_sharing::TimelockError;
+
+fn checkpoint_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("schrodinger-checkpoint-{}-{}", name, std::process::id()))
+}
+
+#[test]
+fn resumable_unlock_recovers_the_key_and_removes_its_checkpoint() {
+    let (puzzle, shards) = shard(b"resumable unlock", 3, 2, [31; 32]);
+    let path = checkpoint_path("round-trip");
+    let puzzle = puzzle.with_checkpoint_interval(1);
+
+    assert_eq!(puzzle.unlock_resumable(&shards, 2, &path).unwrap(), b"resumable unlock");
+    assert!(!path.exists());
+}
+
+#[test]
+fn a_corrupted_or_foreign_checkpoint_is_refused() {
+    let (puzzle, shards) = shard(b"resumable unlock", 3, 2, [32; 32]);
+    let path = checkpoint_path("refused");
+
+    fs::write(&path, b"LCS35CK1 truncated").unwrap();
+    let result = puzzle.unlock_resumable(&shards, 2, &path);
+    assert!(matches!(&result, Err(TimelockError::PuzzleError(message)) if message.contains("corrupted")), "{:?}", result);
+
+    // A well-formed checkpoint written for a different number of squarings
+    let mut other = b"LCS35CK1".to_vec();
+    other.extend_from_slice(&u64::MAX.to_le_bytes());
+    other.extend_from_slice(&0u32.to_le_bytes());
+    fs::write(&path, other).unwrap();
+    let result = puzzle.unlock_resumable(&shards, 2, &path);
+    assert!(matches!(&result, Err(TimelockError::PuzzleError(message)) if message.contains("squarings")), "{:?}", result);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+use timelock_key_sharing::{MaturityClock, Shard};
+
+// Few enough squarings that every command returns at once
+const DIFFICULTY: &str = "4";
+
+fn schrodinger(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_schrödinger"))
+        .args(args)
+        .output()
+        .expect("the schrödinger binary runs")
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("schrodinger-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+// Shard `key` 2-of-3 into `dir/shards`, returning the shard file paths
+fn shard_key(dir: &Path, key: &[u8]) -> Vec<PathBuf> {
+    let key_file = dir.join("key");
+    fs::write(&key_file, key).unwrap();
+    let output_dir = dir.join("shards");
+    let output = schrodinger(&["shard", "--key-file", path(&key_file), "--shards", "3", "--threshold", "2",
+                               "--output-dir", path(&output_dir), "--difficulty", DIFFICULTY]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    (1..=3).map(|index| output_dir.join(format!("shard_{:03}.pem", index))).collect()
+}
+
+#[test]
+fn shard_verify_and_reconstruct_round_trip() {
+    let dir = scratch_dir("round-trip");
+    let key = b"0123456789abcdef0123456789abcdef";
+    let files = shard_key(&dir, key);
+
+    let verify = schrodinger(&["verify", "--shard-files", path(&files[0]), path(&files[2])]);
+    assert!(verify.status.success());
+    assert!(String::from_utf8_lossy(&verify.stdout).contains("2 shards of a 2-of-3 set"));
+
+    let recovered = dir.join("recovered");
+    let reconstruct = schrodinger(&["reconstruct", "--shard-files", path(&files[0]), path(&files[2]),
+                                    "--output", path(&recovered), "--difficulty", DIFFICULTY]);
+    assert!(reconstruct.status.success(), "{}", String::from_utf8_lossy(&reconstruct.stderr));
+    assert_eq!(fs::read(&recovered).unwrap(), key);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn shard_and_key_files_are_readable_only_by_their_owner() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = scratch_dir("permissions");
+    let files = shard_key(&dir, b"0123456789abcdef0123456789abcdef");
+    let recovered = dir.join("recovered");
+    let reconstruct = schrodinger(&["reconstruct", "--shard-files", path(&files[0]), path(&files[1]),
+                                    "--output", path(&recovered), "--difficulty", DIFFICULTY]);
+    assert!(reconstruct.status.success(), "{}", String::from_utf8_lossy(&reconstruct.stderr));
+
+    for file in files.iter().chain([&recovered]) {
+        assert_eq!(fs::metadata(file).unwrap().permissions().mode() & 0o777, 0o600, "{}", file.display());
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reconstruct_refuses_shards_that_have_not_matured() {
+    let dir = scratch_dir("immature");
+    let files = shard_key(&dir, b"0123456789abcdef0123456789abcdef");
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let mut shard = Shard::from_pem(&fs::read_to_string(&files[0]).unwrap()).unwrap();
+    shard.maturity_clock = Some(MaturityClock::new(now + 365 * 24 * 60 * 60));
+    fs::write(&files[0], shard.to_pem()).unwrap();
+
+    let recovered = dir.join("recovered");
+    let output = schrodinger(&["reconstruct", "--shard-files", path(&files[0]), path(&files[1]),
+                               "--output", path(&recovered), "--difficulty", DIFFICULTY]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not released until"));
+    assert!(!recovered.exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_shard_file_exits_with_code_1() {
+    let dir = scratch_dir("missing");
+    let output = schrodinger(&["verify", "--shard-files", path(&dir.join("shard_009.pem"))]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("shard_009.pem"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,17 @@
+// Shards shared by the integration tests. Tiny puzzles keep each case fast;
+// no test depends on their size.
+
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{Shard, LCS35};
+
+pub const DIFFICULTY: u32 = 2;
+pub const MODULUS_BITS: usize = 64;
+
+// `key` split `threshold`-of-`num_shards`, reproducibly for a given seed
+pub fn shard(key: &[u8], num_shards: usize, threshold: usize, seed: [u8; 32]) -> (LCS35, Vec<Shard>) {
+    let mut rng = ChaChaRng::from_seed(seed);
+    let puzzle = LCS35::generate_rsa_modulus_with_rng(DIFFICULTY, MODULUS_BITS, &mut rng);
+    let shards = puzzle.shard(key, num_shards, threshold, &mut rng).unwrap();
+    (puzzle, shards)
+}
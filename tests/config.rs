@@ -0,0 +1,59 @@
+#![cfg(feature = "toml")]
+
+use std::fs;
+use std::path::PathBuf;
+use timelock_key_sharing::{EntropyMeasure, HashAlgorithm, TimelockError, TimelockKeySharding, TimelockKeyShardingBuilder};
+
+fn scratch_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("schrodinger-config-{}-{}.toml", name, std::process::id()))
+}
+
+#[test]
+fn non_default_settings_survive_a_round_trip() {
+    let sharding = TimelockKeyShardingBuilder::default()
+        .difficulty(6)
+        .threshold(3)
+        .hash_algorithm(HashAlgorithm::Blake3)
+        .entropy_threshold(6.5)
+        .entropy_measure(EntropyMeasure::Collision)
+        .borel_tolerance(0.05)
+        .borel_window_bits(5)
+        .min_key_bytes(20)
+        .max_key_bytes(64)
+        .build()
+        .unwrap();
+    let path = scratch_file("round-trip");
+    sharding.write_toml_config(&path).unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+
+    let loaded = TimelockKeySharding::from_toml_config(&path).unwrap();
+    loaded.write_toml_config(&path).unwrap();
+    let rewritten = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    for line in ["entropy_measure = \"collision\"", "borel_window_bits = 5", "min_key_bytes = 20", "max_key_bytes = 64"] {
+        assert!(written.contains(line), "{:?} missing from:\n{}", line, written);
+    }
+    assert_eq!(written, rewritten);
+    assert_eq!(loaded.difficulty(), 6);
+    assert!(matches!(loaded.shard_bytes(&[7; 19], 4), Err(TimelockError::KeyTooShort { len: 19, min: 20 })));
+    assert!(matches!(loaded.shard_bytes(&[7; 65], 4), Err(TimelockError::KeyTooLong { len: 65, max: 64 })));
+}
+
+#[test]
+fn unknown_entropy_measures_are_rejected() {
+    let path = scratch_file("unknown-measure");
+    fs::write(&path, "[timelock]\ndifficulty = 6\nthreshold = 2\nentropy_measure = \"renyi\"\n").unwrap();
+    let result = TimelockKeySharding::from_toml_config(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn key_bounds_from_the_file_go_through_the_builder() {
+    let path = scratch_file("bad-bounds");
+    fs::write(&path, "[timelock]\ndifficulty = 6\nthreshold = 2\nmin_key_bytes = 64\nmax_key_bytes = 32\n").unwrap();
+    let result = TimelockKeySharding::from_toml_config(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))));
+}
@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::Path;
+
+// Identifiers holding MACs and commitments, whatever their type
+const SECRET_IDENTIFIERS: [&str; 2] = ["mac", "commitment"];
+
+// Every `impl SecretData for T` in the crate sources
+fn secret_data_types(sources: &[(String, String)]) -> Vec<String> {
+    sources.iter()
+        .flat_map(|(_, source)| source.lines())
+        .filter_map(|line| line.trim().strip_prefix("impl SecretData for "))
+        .map(|rest| rest.split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or("").to_string())
+        .collect()
+}
+
+fn crate_sources() -> Vec<(String, String)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut sources: Vec<_> = fs::read_dir(root).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap()))
+        .collect();
+    sources.sort();
+    sources
+}
+
+// Words of the item following line `start`: a one-line tuple struct, or
+// everything up to the brace that closes its body. Comments are dropped.
+fn item_words(lines: &[&str], start: usize) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    for line in &lines[start..] {
+        let code = line.split("//").next().unwrap_or("");
+        if code.trim_start().starts_with("#[") {
+            continue;
+        }
+        words.extend(code.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_string));
+        depth += code.matches('{').count();
+        depth = depth.saturating_sub(code.matches('}').count());
+        if depth == 0 && (code.contains('}') || code.trim_end().ends_with(';')) {
+            break;
+        }
+    }
+    words
+}
+
+#[test]
+fn secret_data_is_never_compared_with_eq() {
+    let sources = crate_sources();
+    let mut banned = secret_data_types(&sources);
+    assert!(!banned.is_empty(), "no SecretData implementations found");
+    banned.extend(SECRET_IDENTIFIERS.iter().map(|s| s.to_string()));
+
+    let mut violations = Vec::new();
+    for (file, source) in &sources {
+        for (number, line) in source.lines().enumerate() {
+            let code = line.split("//").next().unwrap_or("");
+            if !code.contains("==") && !code.contains("!=") {
+                continue;
+            }
+            let mentions_secret = code.split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| banned.iter().any(|name| name == word));
+            if mentions_secret {
+                violations.push(format!("{}:{}: {}", file, number + 1, line.trim()));
+            }
+        }
+    }
+
+    assert!(violations.is_empty(), "use ConstantTimeEq::ct_eq instead of ==/!=:\n{}", violations.join("\n"));
+}
+
+// A derived `PartialEq` compares every field with `==`, so types with a MAC or
+// commitment field must implement it by hand. Fields of `SecretData` types are
+// fine: those types compare in constant time themselves.
+#[test]
+fn types_holding_macs_or_commitments_do_not_derive_eq() {
+    let sources = crate_sources();
+    let banned: Vec<String> = SECRET_IDENTIFIERS.iter().map(|s| s.to_string()).collect();
+
+    let mut violations = Vec::new();
+    for (file, source) in &sources {
+        let lines: Vec<&str> = source.lines().collect();
+        for (number, line) in lines.iter().enumerate() {
+            let code = line.split("//").next().unwrap_or("");
+            if !(code.contains("derive(") && code.contains("PartialEq")) {
+                continue;
+            }
+            let words = item_words(&lines, number + 1);
+            if let Some(name) = banned.iter().find(|name| words.contains(name)) {
+                violations.push(format!("{}:{}: holds `{}`: {}", file, number + 1, name, line.trim()));
+            }
+        }
+    }
+
+    assert!(violations.is_empty(), "implement PartialEq with ConstantTimeEq::ct_eq instead of deriving it:\n{}",
+            violations.join("\n"));
+}
+
+#[test]
+fn shards_differing_only_in_their_mac_are_unequal() {
+    use timelock_key_sharing::Shard;
+
+    let shard = Shard::new(1, 3, 2, vec![1, 2, 3, 4]);
+    let mut tagged = shard.clone();
+    tagged.mac = Some(shard.compute_mac(&[1u8; 32]));
+    let mut retagged = shard.clone();
+    retagged.mac = Some(shard.compute_mac(&[2u8; 32]));
+
+    assert_eq!(tagged, tagged.clone());
+    assert_ne!(shard, tagged);
+    assert_ne!(tagged, retagged);
+}
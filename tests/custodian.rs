@@ -0,0 +1,50 @@
+use timelock_key_sharing::{remap_shards, CustodianId, Shard, TimelockError};
+
+mod common;
+use common::shard;
+
+#[test]
+fn remapped_shards_keep_their_x_coordinates() {
+    let (puzzle, shards) = shard(b"delivered to custodians", 3, 2, [10u8; 32]);
+    let mapping = [(3, CustodianId::from("alice")), (1, CustodianId::from("bob"))];
+    let remapped = remap_shards(shards, &mapping, None).unwrap();
+
+    assert_eq!(remapped[0].0, CustodianId::from("alice"));
+    assert_eq!((remapped[0].1.index, remapped[0].1.payload[0]), (1, 3));
+    assert_eq!((remapped[1].1.index, remapped[1].1.payload[0]), (2, 1));
+    assert!(remapped.iter().all(|(_, shard)| shard.mac.is_none()));
+
+    let delivered: Vec<Shard> = remapped.into_iter().map(|(_, shard)| shard).collect();
+    assert_eq!(puzzle.unlock(&delivered, 2).unwrap(), b"delivered to custodians");
+}
+
+#[test]
+fn remapped_shards_are_retagged_with_the_mac_key() {
+    let key = b"retagged for custodians";
+    let (puzzle, shards) = shard(key, 3, 2, [11u8; 32]);
+    let mac_key = Shard::derive_mac_key(key);
+    assert!(shards.iter().all(|shard| shard.verify_mac(&mac_key)));
+
+    let mapping = [(2, CustodianId::from("carol")), (3, CustodianId::from("dave"))];
+    let remapped = remap_shards(shards, &mapping, Some(&mac_key)).unwrap();
+    let delivered: Vec<Shard> = remapped.into_iter().map(|(_, shard)| shard).collect();
+    assert!(delivered.iter().all(|shard| shard.verify_mac(&mac_key)));
+    assert_eq!(puzzle.unlock_verified(&delivered, &mac_key).unwrap(), key);
+
+    // Re-tagging covers the new index, so the shard no longer verifies as dealt
+    let mut renumbered = delivered[0].clone();
+    renumbered.index = 2;
+    assert!(!renumbered.verify_mac(&mac_key));
+}
+
+#[test]
+fn mappings_naming_missing_or_repeated_shards_are_rejected() {
+    let (_, shards) = shard(b"custodian mapping errors", 3, 2, [12u8; 32]);
+    for mapping in [
+        vec![(4, CustodianId::from("erin"))],
+        vec![(1, CustodianId::from("erin")), (1, CustodianId::from("frank"))],
+    ] {
+        let result = remap_shards(shards.clone(), &mapping, None);
+        assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+    }
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::shard;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use timelock_key_sharing::DeadManSwitch;
+
+#[test]
+fn a_missed_checkin_releases_the_shards() {
+    let (_, shards) = shard(b"release on silence", 3, 2, [121; 32]);
+    let (sender, released) = mpsc::channel();
+    let switch = Arc::new(DeadManSwitch::new(shards.clone(), Duration::from_millis(20), move |shards| {
+        sender.send(shards).unwrap();
+    }));
+
+    switch.clone().spawn().join().unwrap();
+    assert!(switch.is_overdue());
+    assert_eq!(released.try_recv().unwrap(), shards);
+    assert!(released.try_recv().is_err());
+}
+
+#[test]
+fn regular_checkins_and_disarming_hold_the_shards_back() {
+    let (_, shards) = shard(b"release on silence", 3, 2, [122; 32]);
+    let (sender, released) = mpsc::channel();
+    let switch = Arc::new(DeadManSwitch::new(shards, Duration::from_millis(500), move |shards| {
+        sender.send(shards).unwrap();
+    }));
+
+    let watcher = switch.clone().spawn();
+    for _ in 0..20 {
+        thread::sleep(Duration::from_millis(50));
+        switch.checkin();
+    }
+    assert!(!switch.is_overdue());
+    switch.disarm();
+    watcher.join().unwrap();
+    assert!(released.try_recv().is_err());
+}
@@ -0,0 +1,26 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{DecoyShardGenerator, Shard, TimelockError, TimelockKeySharding};
+
+const KEY: [u8; 32] = [7; 32];
+
+#[test]
+fn a_decoy_looks_like_a_real_shard_of_the_set() {
+    let shards = TimelockKeySharding::new(4, 2).shard_bytes(&KEY, 3).unwrap();
+    let decoy = DecoyShardGenerator::generate_decoy(2, 3, 2, &mut ChaChaRng::from_seed([101; 32]));
+
+    assert_eq!(decoy.to_bytes().len(), shards[1].to_bytes().len());
+    assert_eq!((decoy.index, decoy.total, decoy.threshold), (2, 3, 2));
+    assert!(decoy.mac.is_some());
+    assert_eq!(Shard::from_bytes(&decoy.to_bytes()).unwrap(), decoy);
+}
+
+#[test]
+fn a_decoy_is_caught_when_combined_with_real_shards() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    let shards = sharding.shard_bytes(&KEY, 3).unwrap();
+    let decoy = DecoyShardGenerator::generate_decoy_for_key_len(2, 3, 2, KEY.len(), &mut ChaChaRng::from_seed([102; 32]));
+
+    let result = sharding.reconstruct_bytes(&[shards[0].clone(), decoy]);
+    assert!(matches!(result, Err(TimelockError::CorruptedShard { index: 1 })), "{:?}", result);
+}
@@ -0,0 +1,29 @@
+use timelock_key_sharing::{TimelockError, TimelockKeySharding};
+
+#[test]
+fn quoted_txt_strings_decode_to_the_same_shard() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    let shards = sharding.shard_bytes(b"published through dns", 3).unwrap();
+
+    let strings = TimelockKeySharding::shard_to_dns_txt(&shards[0]);
+    assert!(strings.len() > 1);
+    assert!(strings.iter().all(|string| string.len() <= 255));
+
+    let zone_file: Vec<String> = strings.iter().map(|string| format!(" \"{}\"", string)).collect();
+    let records: Vec<&str> = zone_file.iter().map(String::as_str).collect();
+    assert_eq!(TimelockKeySharding::shard_from_dns_txt(&records).unwrap(), shards[0]);
+}
+
+#[test]
+fn reordered_or_mangled_txt_strings_are_rejected() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    let shards = sharding.shard_bytes(b"published through dns", 3).unwrap();
+    let strings = TimelockKeySharding::shard_to_dns_txt(&shards[0]);
+
+    let mut records: Vec<&str> = strings.iter().map(String::as_str).collect();
+    records.swap(0, 1);
+    assert!(TimelockKeySharding::shard_from_dns_txt(&records).is_err());
+
+    let result = TimelockKeySharding::shard_from_dns_txt(&["not*base64"]);
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("base64")), "{:?}", result);
+}
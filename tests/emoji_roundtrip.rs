@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use timelock_key_sharing::encoding::emoji::ALPHABET;
+use timelock_key_sharing::{Shard, TimelockError};
+
+mod common;
+use common::shard;
+
+#[test]
+fn emoji_alphabet_is_distinct_and_sorted() {
+    assert_eq!(ALPHABET.iter().collect::<HashSet<_>>().len(), 256);
+    assert!(ALPHABET.windows(2).all(|pair| pair[0] < pair[1]), "alphabet must stay sorted for lookup");
+}
+
+#[test]
+fn shard_emoji_round_trip() {
+    let (_, mut shards) = shard(b"emoji round trip key", 3, 2, [4u8; 32]);
+    shards[0].mac = None;
+
+    for shard in shards {
+        let emoji = shard.to_emoji();
+        assert_eq!(emoji.chars().count(), shard.to_bytes().len());
+        let reencoded = String::from_utf8(emoji.into_bytes()).unwrap();
+        assert_eq!(Shard::from_emoji(&reencoded).unwrap(), shard);
+    }
+}
+
+#[test]
+fn pasted_whitespace_and_presentation_selectors_are_ignored() {
+    let (_, shards) = shard(b"emoji paste key", 3, 2, [5u8; 32]);
+    let pasted: String = shards[1].to_emoji().chars()
+        .enumerate()
+        .flat_map(|(i, c)| [Some(c), (i % 2 == 0).then_some('\u{fe0f}'), (i % 8 == 7).then_some('\n')])
+        .flatten()
+        .collect();
+    assert_eq!(Shard::from_emoji(&pasted).unwrap(), shards[1]);
+}
+
+#[test]
+fn bad_emoji_are_rejected() {
+    let (_, shards) = shard(b"emoji rejection key", 3, 2, [6u8; 32]);
+    let emoji = shards[0].to_emoji();
+
+    let foreign = emoji.replacen(ALPHABET[shards[0].to_bytes()[0] as usize], "🎉", 1);
+    let result = Shard::from_emoji(&foreign);
+    assert!(matches!(result, Err(TimelockError::InvalidShardFormat(_))), "{:?}", result);
+
+    let truncated: String = emoji.chars().take(3).collect();
+    let result = Shard::from_emoji(&truncated);
+    assert!(matches!(result, Err(TimelockError::InvalidShardFormat(_))), "{:?}", result);
+    assert!(matches!(Shard::from_emoji(""), Err(TimelockError::InvalidShardFormat(_))));
+
+    // A misread symbol from the alphabet still decodes; the checksum catches it
+    let mut chars: Vec<char> = emoji.chars().collect();
+    let last = chars.len() - 1 - shards[0].mac.map_or(0, |mac| mac.len());
+    let position = ALPHABET.binary_search(&chars[last]).unwrap();
+    chars[last] = ALPHABET[(position + 1) % 256];
+    let misread = Shard::from_emoji(&chars.into_iter().collect::<String>()).unwrap();
+    assert!(!misread.verify_checksum());
+}
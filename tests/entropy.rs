@@ -0,0 +1,21 @@
+use timelock_key_sharing::entropy::check_borel_regularity_windowed;
+use timelock_key_sharing::TimelockKeySharding;
+
+#[test]
+fn out_of_range_borel_windows_fail_instead_of_panicking() {
+    let data: Vec<u8> = (0..=255).collect();
+    assert!(check_borel_regularity_windowed(&data, 8, 0.5));
+    assert!(!check_borel_regularity_windowed(&data, 0, 0.5));
+    assert!(!check_borel_regularity_windowed(&data, 17, 0.5));
+}
+
+#[test]
+fn builder_clamps_the_borel_window() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    let shards = sharding.shard_bytes(b"clamped borel window size", 3).unwrap();
+
+    for window_bits in [0, 64] {
+        let report = sharding.clone().with_borel_window_bits(window_bits).check_shard_entropy(&shards);
+        assert_eq!(report.per_shard.len(), 3);
+    }
+}
@@ -0,0 +1,39 @@
+use timelock_key_sharing::field::{eval_poly, interpolate_at_zero};
+use timelock_key_sharing::{FiniteField, GFp, GF256};
+
+#[test]
+fn gf256_matches_the_aes_field() {
+    // FIPS-197 section 4.2
+    assert_eq!(GF256::mul(GF256(0x57), GF256(0x83)), GF256(0xc1));
+    assert_eq!(GF256::mul(GF256(0x57), GF256(0x13)), GF256(0xfe));
+    assert_eq!(GF256::add(GF256(0x57), GF256(0x83)), GF256(0xd4));
+
+    for a in 1..=255u8 {
+        let inverse = GF256::inv(GF256(a)).unwrap();
+        assert_eq!(GF256::mul(GF256(a), inverse), GF256(1), "{:#04x}", a);
+    }
+    assert_eq!(GF256::inv(GF256(0)), None);
+}
+
+#[test]
+fn interpolation_recovers_the_constant_term() {
+    let coefficients = [GF256(0x2a), GF256(0x07), GF256(0xe1)];
+    let points: Vec<_> = [3u8, 9, 200].iter()
+        .map(|&x| (GF256(x), eval_poly(&coefficients, GF256(x))))
+        .collect();
+    assert_eq!(interpolate_at_zero(&points), GF256(0x2a));
+
+    let xs: Vec<GF256> = points.iter().map(|&(x, _)| x).collect();
+    let recombined = points.iter()
+        .enumerate()
+        .map(|(i, &(_, y))| GF256::mul(y, GF256::lagrange_at_zero(&xs, i)))
+        .fold(GF256(0), GF256::add);
+    assert_eq!(recombined, GF256(0x2a));
+
+    let prime = 65_521;
+    let coefficients = [GFp::new(1234, prime), GFp::new(99, prime)];
+    let points: Vec<_> = [1u64, 2].iter()
+        .map(|&x| (GFp::new(x, prime), eval_poly(&coefficients, GFp::new(x, prime))))
+        .collect();
+    assert_eq!(interpolate_at_zero(&points).value(), 1234);
+}
@@ -0,0 +1,26 @@
+use timelock_key_sharing::{Shard, TimelockError, CURRENT_VERSION};
+
+mod common;
+use common::shard;
+
+#[test]
+fn every_format_version_round_trips() {
+    let (_, shards) = shard(b"older formats stay readable", 3, 2, [6u8; 32]);
+    for version in 1..=CURRENT_VERSION {
+        let mut shard = shards[1].clone();
+        shard.version = version;
+        shard.mac = Some(shard.compute_mac(&[8u8; 32]));
+        assert_eq!(Shard::from_bytes(&shard.to_bytes()).unwrap(), shard, "version {}", version);
+    }
+}
+
+#[test]
+fn unknown_format_versions_are_rejected() {
+    let (_, shards) = shard(b"only known layouts decode", 3, 2, [7u8; 32]);
+    for version in [0, CURRENT_VERSION + 1] {
+        let mut bytes = shards[0].to_bytes();
+        bytes[0] = version;
+        let result = Shard::from_bytes(&bytes);
+        assert!(matches!(result, Err(TimelockError::InvalidShardFormat(_))), "version {}: {:?}", version, result);
+    }
+}
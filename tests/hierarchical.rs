@@ -0,0 +1,52 @@
+use timelock_key_sharing::{reconstruct_hierarchical, shard_hierarchical, HierarchicalSharding, LevelConfig, TimelockError};
+
+// Three regions, any two of which are needed, each with 2-of-3 officers
+fn config() -> HierarchicalSharding {
+    HierarchicalSharding::new(vec![
+        LevelConfig { num_shards: 3, threshold: 2 },
+        LevelConfig { num_shards: 3, threshold: 2 },
+    ]).unwrap()
+}
+
+#[test]
+fn enough_members_of_enough_groups_recover_the_key() {
+    let key = b"hierarchical master key";
+    let config = config();
+    let mut groups = shard_hierarchical(key, &config);
+    assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), [3, 3, 3]);
+
+    groups[0].truncate(2);
+    groups[1].clear();
+    groups[2].remove(0);
+    assert_eq!(reconstruct_hierarchical(groups, &config).unwrap(), key);
+}
+
+#[test]
+fn too_few_members_or_invalid_levels_are_rejected() {
+    let config = config();
+    let mut groups = shard_hierarchical(b"hierarchical master key", &config);
+    groups[0].truncate(1);
+    groups[1].clear();
+    let result = reconstruct_hierarchical(groups, &config);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { provided: 4, required: 4 })), "{:?}", result);
+
+    let groups = shard_hierarchical(b"hierarchical master key", &config);
+    let mut doubled = groups.clone();
+    doubled[0].push(groups[0][0].clone());
+    assert!(matches!(reconstruct_hierarchical(doubled, &config), Err(TimelockError::DuplicateShard { index: 1 })));
+
+    assert!(matches!(HierarchicalSharding::new(Vec::new()), Err(TimelockError::InvalidConfiguration(_))));
+    let result = HierarchicalSharding::new(vec![LevelConfig { num_shards: 3, threshold: 4 }]);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialized_configurations_are_checked() {
+    let decoded: HierarchicalSharding = serde_json::from_str(&serde_json::to_string(&config()).unwrap()).unwrap();
+    assert_eq!(decoded, config());
+
+    for json in [r#"{"levels":[]}"#, r#"{"levels":[{"num_shards":3,"threshold":0}]}"#] {
+        assert!(serde_json::from_str::<HierarchicalSharding>(json).is_err(), "{}", json);
+    }
+}
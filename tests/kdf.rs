@@ -0,0 +1,35 @@
+mod common;
+
+use common::shard;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use timelock_key_sharing::{KeyDerivationRatchet, TimelockError};
+
+const MASTER_KEY: [u8; 32] = [0x5a; 32];
+
+#[test]
+fn a_shard_encrypted_under_its_derived_key_decrypts_with_the_same_derivation() {
+    let (_, shards) = shard(b"transport keys", 3, 2, [111; 32]);
+    let ratchet = KeyDerivationRatchet::new(MASTER_KEY);
+
+    let mut expected = [0u8; 32];
+    Hkdf::<Sha256>::new(None, &MASTER_KEY).expand(b"shard-2", &mut expected).unwrap();
+    assert_eq!(ratchet.derive_shard_key(2), expected);
+
+    let encrypted = shards[1].encrypt(&ratchet.derive_shard_key(2));
+    let reopened = KeyDerivationRatchet::new(MASTER_KEY);
+    assert_eq!(encrypted.decrypt(&reopened.derive_shard_key(2)).unwrap(), shards[1]);
+}
+
+#[test]
+fn another_shards_key_does_not_decrypt() {
+    let (_, shards) = shard(b"transport keys", 3, 2, [112; 32]);
+    let ratchet = KeyDerivationRatchet::new(MASTER_KEY);
+    let encrypted = shards[0].encrypt(&ratchet.derive_shard_key(1));
+
+    assert_ne!(ratchet.derive_shard_key(1), ratchet.derive_shard_key(2));
+    assert!(matches!(encrypted.decrypt(&ratchet.derive_shard_key(2)), Err(TimelockError::DecryptionFailed)));
+    assert!(matches!(encrypted.decrypt(&KeyDerivationRatchet::new([0; 32]).derive_shard_key(1)),
+                     Err(TimelockError::DecryptionFailed)));
+    assert_eq!(format!("{:?}", ratchet), "KeyDerivationRatchet { .. }");
+}
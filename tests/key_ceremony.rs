@@ -0,0 +1,50 @@
+mod common;
+
+use common::shard;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{KeyCeremony, ParticipantId, TimelockError, CEREMONY_KEY_LEN};
+
+fn participants(count: u32) -> Vec<ParticipantId> {
+    (1..=count).map(ParticipantId).collect()
+}
+
+#[test]
+fn ceremony_completes_once_every_participant_accepts() {
+    let mut ceremony = KeyCeremony::new(participants(3), 2).unwrap();
+    let key = ceremony.step1_generate_key(&mut ChaChaRng::from_seed([5; 32]));
+    assert_eq!(key.len(), CEREMONY_KEY_LEN);
+    let (_, shards) = shard(&key, 3, 2, [6; 32]);
+    let commitments = ceremony.step2_commit_shards(&shards);
+
+    for (i, (shard, commitment)) in shards.iter().zip(&commitments).enumerate() {
+        assert!(!ceremony.finalize().complete);
+        ceremony.step3_verify_and_accept(ParticipantId(i as u32 + 1), shard, commitment).unwrap();
+    }
+    let record = ceremony.finalize();
+    assert!(record.complete);
+    assert_eq!(record.accepted, participants(3));
+    assert_eq!(record.threshold, 2);
+}
+
+#[test]
+fn a_shard_that_does_not_match_its_commitment_is_refused() {
+    let mut ceremony = KeyCeremony::new(participants(3), 2).unwrap();
+    let key = ceremony.step1_generate_key(&mut ChaChaRng::from_seed([7; 32]));
+    let (_, shards) = shard(&key, 3, 2, [8; 32]);
+    let commitments = ceremony.step2_commit_shards(&shards);
+
+    let result = ceremony.step3_verify_and_accept(ParticipantId(1), &shards[1], &commitments[0]);
+    assert!(matches!(result, Err(TimelockError::CorruptedShard { index: 0 })), "{:?}", result);
+    let result = ceremony.step3_verify_and_accept(ParticipantId(9), &shards[0], &commitments[0]);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+    assert!(ceremony.finalize().accepted.is_empty());
+}
+
+#[test]
+fn threshold_outside_the_participant_count_is_rejected() {
+    for threshold in [0, 1, 4] {
+        let result = KeyCeremony::new(participants(3), threshold);
+        assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "threshold {}", threshold);
+    }
+}
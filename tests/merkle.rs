@@ -0,0 +1,58 @@
+use timelock_key_sharing::build_shard_merkle_tree;
+
+mod common;
+use common::shard;
+
+#[test]
+fn every_shard_proves_membership_and_no_other() {
+    let (_, shards) = shard(b"merkle membership key", 5, 2, [7u8; 32]);
+
+    let tree = build_shard_merkle_tree(&shards);
+    let root = tree.root();
+    for (index, shard) in shards.iter().enumerate() {
+        let proof = tree.proof(index);
+        assert!(proof.verify(&root, shard), "shard {}", index);
+        assert!(!proof.verify(&root, &shards[(index + 1) % shards.len()]));
+    }
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let (_, shards) = shard(b"merkle tamper key", 5, 2, [8u8; 32]);
+    let tree = build_shard_merkle_tree(&shards);
+    let root = tree.root();
+    let proof = tree.proof(2);
+    assert!(proof.verify(&root, &shards[2]));
+
+    let mut flipped = proof.clone();
+    flipped.siblings[0][0] ^= 1;
+    assert!(!flipped.verify(&root, &shards[2]));
+
+    let mut moved = proof.clone();
+    moved.index = 3;
+    assert!(!moved.verify(&root, &shards[2]));
+
+    let mut out_of_range = proof.clone();
+    out_of_range.index = proof.leaf_count;
+    assert!(!out_of_range.verify(&root, &shards[2]));
+
+    let mut short = proof.clone();
+    short.siblings.pop();
+    assert!(!short.verify(&root, &shards[2]));
+
+    let mut long = proof.clone();
+    long.siblings.push(root);
+    assert!(!long.verify(&root, &shards[2]));
+
+    let mut resized = proof.clone();
+    resized.leaf_count = 4;
+    assert!(!resized.verify(&root, &shards[2]));
+
+    let mut other_root = root;
+    other_root[31] ^= 1;
+    assert!(!proof.verify(&other_root, &shards[2]));
+
+    let mut altered = shards[2].clone();
+    altered.payload[0] ^= 1;
+    assert!(!proof.verify(&root, &altered));
+}
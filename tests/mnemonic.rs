@@ -0,0 +1,35 @@
+mod common;
+
+use common::shard;
+use timelock_key_sharing::{MnemonicShard, TimelockError};
+
+#[test]
+fn mnemonic_text_decodes_to_the_same_shard() {
+    let (_, shards) = shard(b"paper backup key", 3, 2, [21; 32]);
+    for original in &shards {
+        let mnemonic = MnemonicShard::from_shard(original);
+        let text = mnemonic.to_string();
+        assert!(text.lines().all(|line| line.split(' ').count() <= 12));
+
+        let read_back: MnemonicShard = text.to_uppercase().parse().unwrap();
+        assert_eq!(read_back, mnemonic);
+        assert_eq!(read_back.to_shard().unwrap(), *original);
+    }
+}
+
+#[test]
+fn a_mistranscribed_word_is_rejected() {
+    let (_, shards) = shard(b"paper backup key", 3, 2, [22; 32]);
+    let mut words = MnemonicShard::from_shard(&shards[0]).words().to_vec();
+
+    let swapped = if words[10] == "zoo" { "abandon" } else { "zoo" };
+    words[10] = swapped.to_string();
+    let result = words.join(" ").parse::<MnemonicShard>().unwrap().to_shard();
+    assert!(matches!(result, Err(TimelockError::InvalidShardFormat(_))), "{:?}", result);
+
+    words[10] = "notaword".to_string();
+    let result = words.join(" ").parse::<MnemonicShard>().unwrap().to_shard();
+    assert!(matches!(result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("notaword")));
+
+    assert!(matches!("   ".parse::<MnemonicShard>(), Err(TimelockError::InvalidShardFormat(_))));
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::shard;
+use timelock_key_sharing::{reassemble_from_nfc, split_for_nfc, TimelockError};
+
+fn rejection(records: &[Vec<u8>]) -> String {
+    match reassemble_from_nfc(records) {
+        Err(TimelockError::InvalidShardFormat(message)) => message,
+        other => panic!("expected InvalidShardFormat, got {:?}", other),
+    }
+}
+
+#[test]
+fn records_in_any_order_reassemble_the_shard() {
+    let (_, shards) = shard(b"tap to transfer", 3, 2, [81; 32]);
+    let mut records = split_for_nfc(&shards[0], 16);
+    assert!(records.len() > 2);
+    assert!(records.iter().all(|record| record.len() <= 16));
+
+    records.reverse();
+    assert_eq!(reassemble_from_nfc(&records).unwrap(), shards[0]);
+}
+
+#[test]
+fn missing_duplicate_or_foreign_records_are_rejected() {
+    let (_, shards) = shard(b"tap to transfer", 3, 2, [82; 32]);
+    let records = split_for_nfc(&shards[0], 16);
+
+    assert!(rejection(&records[1..]).contains("record 0 of"));
+
+    let mut duplicated = records.clone();
+    duplicated.push(records[1].clone());
+    assert!(rejection(&duplicated).contains("more than once"));
+
+    let mut mixed = records.clone();
+    mixed[1] = split_for_nfc(&shards[1], 16)[1].clone();
+    assert!(rejection(&mixed).contains("different shards"));
+
+    assert!(rejection(&[]).contains("no NFC records"));
+}
@@ -0,0 +1,23 @@
+use timelock_key_sharing::{derive_key_from_passphrase, Argon2Params, TimelockError, TimelockKeySharding};
+
+// Cheap enough for a test; real use keeps the defaults
+const PARAMS: Argon2Params = Argon2Params { m_cost: 64, t_cost: 1, p_cost: 1 };
+
+#[test]
+fn the_sharded_key_is_derived_again_from_the_passphrase_and_salt() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    let salt = [3u8; 32];
+
+    let (shards, key) = sharding.shard_passphrase("correct horse battery staple", &salt, 3, PARAMS).unwrap();
+    assert_eq!(derive_key_from_passphrase("correct horse battery staple", &salt, PARAMS), key);
+    assert_eq!(sharding.reconstruct_bytes(&shards[1..]).unwrap(), key);
+}
+
+#[test]
+fn out_of_range_argon2_costs_are_rejected() {
+    let sharding = TimelockKeySharding::new(4, 2);
+    for params in [Argon2Params { m_cost: 1, ..PARAMS }, Argon2Params { t_cost: 0, ..PARAMS }, Argon2Params { p_cost: 0, ..PARAMS }] {
+        let result = sharding.shard_passphrase("passphrase", &[0; 32], 3, params);
+        assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", params);
+    }
+}
@@ -0,0 +1,32 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{TimelockError, LCS35};
+
+mod common;
+use common::{shard, DIFFICULTY};
+
+#[test]
+fn puzzle_from_exported_params_unlocks_but_cannot_shard() {
+    let (puzzle, shards) = shard(b"solved by a third party", 3, 2, [8u8; 32]);
+    let params = puzzle.export_params();
+    assert_eq!(params.iterations, 1 << DIFFICULTY);
+
+    let imported = LCS35::from_params(&params).unwrap();
+    assert_eq!(imported.export_params(), params);
+    assert_eq!(imported.unlock(&shards[1..], 2).unwrap(), b"solved by a third party");
+
+    let mut rng = ChaChaRng::from_seed([9u8; 32]);
+    assert!(matches!(imported.shard(b"no trapdoor", 3, 2, &mut rng), Err(TimelockError::PuzzleError(_))));
+}
+
+#[test]
+fn params_with_mismatched_iterations_are_rejected() {
+    let (puzzle, _) = shard(b"tampered parameters", 3, 2, [9u8; 32]);
+    let mut params = puzzle.export_params();
+    params.iterations += 1;
+    assert!(matches!(LCS35::from_params(&params), Err(TimelockError::InvalidConfiguration(_))));
+
+    let mut params = puzzle.export_params();
+    params.modulus_hex = Some("not hex".to_string());
+    assert!(LCS35::from_params(&params).is_err());
+}
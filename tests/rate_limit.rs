@@ -0,0 +1,59 @@
+use std::fs;
+use std::time::Duration;
+use timelock_key_sharing::{ReconstructRateLimiter, TimelockError, TimelockKeySharding};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+#[test]
+fn successful_reconstructions_are_not_limited() {
+    let limiter = ReconstructRateLimiter::new(1, HOUR);
+    let sharding = TimelockKeySharding::new(4, 2).with_rate_limiter(limiter.clone());
+    let shards = sharding.shard_bytes(b"rate limited key material", 3).unwrap();
+
+    for pair in [&shards[..2], &shards[1..]] {
+        assert_eq!(sharding.reconstruct_bytes(pair).unwrap(), b"rate limited key material");
+    }
+    assert_eq!(limiter.recent_failures(), 0);
+}
+
+#[test]
+fn repeated_failures_lock_out_even_valid_shards() {
+    let sharding = TimelockKeySharding::new(4, 2).with_rate_limiter(ReconstructRateLimiter::new(2, HOUR));
+    let shards = sharding.shard_bytes(b"rate limited key material", 3).unwrap();
+
+    for _ in 0..2 {
+        assert!(matches!(sharding.reconstruct_bytes(&shards[..1]), Err(TimelockError::InsufficientShards { .. })));
+    }
+    let result = sharding.reconstruct_bytes(&shards[..2]);
+    assert!(matches!(result, Err(TimelockError::RateLimited { retry_after }) if retry_after <= HOUR), "{:?}", result);
+}
+
+#[test]
+fn persistent_failures_survive_a_restart() {
+    let path = std::env::temp_dir().join(format!("schrodinger-rate-limit-{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    ReconstructRateLimiter::persistent(1, HOUR, &path).unwrap().record_failure().unwrap();
+    let reopened = ReconstructRateLimiter::persistent(1, HOUR, &path).unwrap();
+    assert_eq!(reopened.recent_failures(), 1);
+    assert!(matches!(reopened.check(), Err(TimelockError::RateLimited { .. })));
+
+    fs::write(&path, "yesterday\n").unwrap();
+    let result = ReconstructRateLimiter::persistent(1, HOUR, &path);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result.map(|_| ()));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn parallel_attempts_share_the_limit() {
+    let limiter = ReconstructRateLimiter::new(2, HOUR);
+    let reservations: Vec<_> = (0..2).map(|_| limiter.reserve().unwrap()).collect();
+    assert!(matches!(limiter.reserve(), Err(TimelockError::RateLimited { .. })));
+
+    limiter.release(reservations[0]);
+    assert_eq!(limiter.recent_failures(), 1);
+    let sharding = TimelockKeySharding::new(4, 2).with_rate_limiter(limiter.clone());
+    let shards = sharding.shard_bytes(b"rate limited key material", 3).unwrap();
+    assert_eq!(sharding.reconstruct_bytes(&shards[..2]).unwrap(), b"rate limited key material");
+    assert_eq!(limiter.recent_failures(), 1);
+}
@@ -0,0 +1,17 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{reshare, SecretSplitter, ShamirSplit};
+
+#[test]
+fn reshared_set_recovers_the_same_key() {
+    let key = b"reshare without reconstructing";
+    let mut rng = ChaChaRng::from_seed([9u8; 32]);
+    let old_shards = ShamirSplit.split(key, 5, 3, &mut rng).unwrap();
+
+    let new_shards = reshare(&old_shards[1..4], 4, 7, &mut rng).unwrap();
+    assert_eq!(new_shards.len(), 7);
+    assert!(new_shards.iter().all(|shard| shard.threshold == 4 && shard.total == 7));
+
+    assert_eq!(ShamirSplit.combine(&new_shards[2..6]).unwrap(), key);
+    assert!(ShamirSplit.combine(&new_shards[..3]).is_err());
+}
@@ -0,0 +1,88 @@
+use proptest::prelude::*;
+use timelock_key_sharing::{Shard, TimelockError};
+
+mod common;
+use common::shard;
+
+// (num_shards, threshold, shard indices in random order)
+fn sharing_params() -> impl Strategy<Value = (usize, usize, Vec<usize>)> {
+    (2..=10usize)
+        .prop_flat_map(|num_shards| (Just(num_shards), 2..=num_shards))
+        .prop_flat_map(|(num_shards, threshold)| {
+            (Just(num_shards), Just(threshold), Just((0..num_shards).collect::<Vec<_>>()).prop_shuffle())
+        })
+}
+
+fn pick(shards: &[Shard], indices: &[usize]) -> Vec<Shard> {
+    indices.iter().map(|&i| shards[i].clone()).collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn any_threshold_subset_reconstructs_the_key(
+        key in prop::collection::vec(any::<u8>(), 1..=256),
+        (num_shards, threshold, order) in sharing_params(),
+        seed in any::<[u8; 32]>(),
+    ) {
+        let (puzzle, shards) = shard(&key, num_shards, threshold, seed);
+        let subset = pick(&shards, &order[..threshold]);
+        prop_assert_eq!(puzzle.unlock(&subset, threshold).unwrap(), key);
+    }
+
+    #[test]
+    fn too_few_shards_are_rejected(
+        key in prop::collection::vec(any::<u8>(), 1..=256),
+        (num_shards, threshold, order) in sharing_params(),
+        seed in any::<[u8; 32]>(),
+    ) {
+        let (puzzle, shards) = shard(&key, num_shards, threshold, seed);
+        let subset = pick(&shards, &order[..threshold - 1]);
+        let result = puzzle.unlock(&subset, threshold);
+        prop_assert!(matches!(result, Err(TimelockError::InsufficientShards { .. })), "{:?}", result);
+    }
+
+    #[test]
+    fn duplicate_shards_are_rejected(
+        key in prop::collection::vec(any::<u8>(), 1..=256),
+        (num_shards, threshold, order) in sharing_params(),
+        seed in any::<[u8; 32]>(),
+    ) {
+        let (puzzle, shards) = shard(&key, num_shards, threshold, seed);
+        let mut subset = pick(&shards, &order[..threshold]);
+        subset[threshold - 1] = subset[0].clone();
+        let result = puzzle.unlock(&subset, threshold);
+        prop_assert!(matches!(result, Err(TimelockError::DuplicateShard { .. })), "{:?}", result);
+    }
+
+    #[test]
+    fn corrupted_payload_bytes_never_yield_the_key(
+        key in prop::collection::vec(any::<u8>(), 1..=256),
+        (num_shards, threshold, order) in sharing_params(),
+        seed in any::<[u8; 32]>(),
+        position in any::<prop::sample::Index>(),
+        flip in 1..=255u8,
+    ) {
+        let (puzzle, shards) = shard(&key, num_shards, threshold, seed);
+        let mut subset = pick(&shards, &order[..threshold]);
+        let payload = &mut subset[0].payload;
+        let position = position.index(payload.len());
+        payload[position] ^= flip;
+
+        // Without MACs the damage may go unnoticed, but never as the original key
+        prop_assert!(puzzle.unlock(&subset, threshold).map_or(true, |recovered| recovered != key));
+
+        let mac_key = Shard::derive_mac_key(&key);
+        let result = puzzle.unlock_verified(&subset, &mac_key);
+        prop_assert!(matches!(result, Err(TimelockError::CorruptedShard { index: 0 })), "{:?}", result);
+    }
+}
+
+#[test]
+fn repeated_shard_is_rejected_before_reconstruction() {
+    let (puzzle, shards) = shard(b"one custodian, two copies", 3, 2, [4u8; 32]);
+    let repeated = [shards[0].clone(), shards[0].clone(), shards[2].clone()];
+    let result = puzzle.unlock(&repeated, 2);
+    assert!(matches!(result, Err(TimelockError::DuplicateShard { index: 1 })), "{:?}", result);
+}
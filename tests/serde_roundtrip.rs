@@ -0,0 +1,47 @@
+#![cfg(feature = "serde")]
+
+use timelock_key_sharing::{EncryptedShard, Shard};
+
+mod common;
+use common::shard;
+
+#[test]
+fn shard_json_round_trip() {
+    let (_, shards) = shard(b"serde round trip key", 3, 2, [0u8; 32]);
+
+    for shard in shards {
+        let json = serde_json::to_string(&shard).unwrap();
+        assert_eq!(serde_json::from_str::<Shard>(&json).unwrap(), shard);
+    }
+}
+
+#[test]
+fn shard_payload_is_hex_encoded() {
+    let shard = Shard {
+        index: 1,
+        total: 3,
+        threshold: 2,
+        version: 1,
+        payload: vec![0xde, 0xad, 0xbe, 0xef],
+        checksum: crc32fast::hash(&[0xde, 0xad, 0xbe, 0xef]),
+        mac: None,
+        maturity_clock: None,
+    };
+
+    let json = serde_json::to_value(&shard).unwrap();
+    assert_eq!(json["payload"], "deadbeef");
+    assert_eq!(json["index"], 1);
+}
+
+#[test]
+fn encrypted_shard_json_round_trip() {
+    let (_, shards) = shard(b"serde round trip key", 3, 2, [1u8; 32]);
+    let key = [9u8; 32];
+
+    let encrypted = shards[0].encrypt(&key);
+    let json = serde_json::to_value(&encrypted).unwrap();
+    assert_eq!(json["ciphertext"], encrypted.to_hex());
+
+    let decoded: EncryptedShard = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.decrypt(&key).unwrap(), shards[0]);
+}
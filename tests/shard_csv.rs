@@ -0,0 +1,38 @@
+mod common;
+
+use common::shard;
+use timelock_key_sharing::{read_shard_csv, write_shard_csv, Shard, TimelockError};
+
+#[test]
+fn a_written_sheet_reads_back_without_macs() {
+    let (_, shards) = shard(b"kept in a spreadsheet", 3, 2, [141; 32]);
+    let mut sheet = Vec::new();
+    write_shard_csv(&shards, &mut sheet).unwrap();
+
+    let read = read_shard_csv(sheet.as_slice()).unwrap();
+    let expected: Vec<Shard> = shards.into_iter()
+        .map(|mut shard| {
+            shard.mac = None;
+            shard
+        })
+        .collect();
+    assert_eq!(read, expected);
+
+    let quoted = format!(" \"{}\" ", expected[0].to_csv_row().replace(',', "\",\""));
+    assert_eq!(Shard::from_csv_row(&quoted).unwrap(), expected[0]);
+}
+
+#[test]
+fn a_malformed_row_is_rejected_with_its_line_number() {
+    let (_, shards) = shard(b"kept in a spreadsheet", 3, 2, [142; 32]);
+    let mut sheet = Vec::new();
+    write_shard_csv(&shards, &mut sheet).unwrap();
+    let sheet = String::from_utf8(sheet).unwrap().replacen(",3,2,", ",3,two,", 1);
+
+    let result = read_shard_csv(sheet.as_bytes());
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message))
+                     if message.starts_with("line 2:") && message.contains("threshold")), "{:?}", result);
+
+    let result = Shard::from_csv_row("1,3,2,3");
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("found 4")), "{:?}", result);
+}
@@ -0,0 +1,43 @@
+use std::fs;
+use timelock_key_sharing::{TimelockError, TimelockKeySharding};
+
+#[test]
+fn shards_written_to_a_directory_reconstruct_the_key() {
+    let dir = std::env::temp_dir().join(format!("schrodinger-shard-files-{}", std::process::id()));
+    let sharding = TimelockKeySharding::new(4, 3);
+    let key = b"directory round trip key";
+
+    let paths = sharding.split_key_to_files(key, 5, &dir).unwrap();
+    let names: Vec<_> = paths.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+    assert_eq!(names, ["shard_001.pem", "shard_002.pem", "shard_003.pem", "shard_004.pem", "shard_005.pem"]);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&paths[0]).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    assert_eq!(sharding.reconstruct_from_directory(&dir).unwrap(), key);
+
+    for path in &paths[1..4] {
+        fs::remove_file(path).unwrap();
+    }
+    let result = sharding.reconstruct_from_directory(&dir);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { provided: 2, required: 3 })), "{:?}", result);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_failed_write_leaves_no_shards_behind() {
+    let dir = std::env::temp_dir().join(format!("schrodinger-shard-files-blocked-{}", std::process::id()));
+    fs::create_dir_all(dir.join("shard_003.pem")).unwrap();
+    let sharding = TimelockKeySharding::new(4, 3);
+
+    let result = sharding.split_key_to_files(b"blocked on the third shard", 5, &dir);
+    assert!(matches!(result, Err(TimelockError::Io(_))), "{:?}", result);
+    let left: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(left, ["shard_003.pem"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
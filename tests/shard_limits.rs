@@ -0,0 +1,21 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{TimelockError, LCS35, MAX_SHARDS};
+
+mod common;
+use common::{shard, DIFFICULTY, MODULUS_BITS};
+
+#[test]
+fn shard_counts_beyond_one_byte_are_rejected() {
+    let mut rng = ChaChaRng::from_seed([7u8; 32]);
+    let puzzle = LCS35::generate_rsa_modulus_with_rng(DIFFICULTY, MODULUS_BITS, &mut rng);
+    let result = puzzle.shard(b"too many custodians", MAX_SHARDS + 1, 2, &mut rng);
+    assert!(matches!(result, Err(TimelockError::TooManyShards { requested: 256, max: 255 })), "{:?}", result);
+}
+
+#[test]
+fn the_largest_shard_count_round_trips() {
+    let (puzzle, shards) = shard(b"every custodian", MAX_SHARDS, 2, [8u8; 32]);
+    assert_eq!(shards.last().unwrap().index as usize, MAX_SHARDS);
+    assert_eq!(puzzle.unlock(&shards[MAX_SHARDS - 2..], 2).unwrap(), b"every custodian");
+}
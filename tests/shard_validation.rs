@@ -0,0 +1,72 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{SecretSplitter, ShamirSplit, Shard, TimelockError, TimelockKeySharding, XorSplit};
+
+mod common;
+use common::shard;
+
+fn is_invalid_format(result: Result<Shard, TimelockError>) -> bool {
+    matches!(result, Err(TimelockError::InvalidShardFormat(_)))
+}
+
+#[test]
+fn inconsistent_shards_are_rejected_when_decoded() {
+    let (_, shards) = shard(b"validated on decode", 3, 2, [5u8; 32]);
+    let bytes = shards[0].to_bytes();
+    assert!(Shard::from_bytes(&bytes).is_ok());
+
+    // Index zero
+    let mut bytes = shards[0].to_bytes();
+    bytes[1] = 0;
+    assert!(is_invalid_format(Shard::from_bytes(&bytes)));
+
+    // Threshold of one
+    let mut bytes = shards[0].to_bytes();
+    bytes[3] = 1;
+    assert!(is_invalid_format(Shard::from_bytes(&bytes)));
+
+    // Threshold beyond the total
+    let mut bytes = shards[0].to_bytes();
+    bytes[3] = 4;
+    assert!(is_invalid_format(Shard::from_bytes(&bytes)));
+
+    // A bare x-coordinate
+    let mut truncated = shards[0].clone();
+    truncated.payload.truncate(1);
+    truncated.mac = None;
+    assert!(is_invalid_format(Shard::from_bytes(&truncated.to_bytes())));
+}
+
+#[test]
+fn short_key_shards_from_plain_splitters_decode() {
+    let key = [0x5au8; 16];
+    let mut rng = ChaChaRng::from_seed([6u8; 32]);
+    let splitters: [(&dyn SecretSplitter, usize); 2] = [(&XorSplit, 3), (&ShamirSplit, 2)];
+    for (splitter, threshold) in splitters {
+        let shards = splitter.split(&key, 3, threshold, &mut rng).unwrap();
+        assert!(shards.iter().all(|shard| shard.payload.len() == 1 + key.len()));
+
+        let decoded: Vec<Shard> = shards.iter()
+            .map(|shard| Shard::from_bytes(&shard.to_bytes()).unwrap())
+            .collect();
+        assert_eq!(decoded, shards, "{:?}", splitter);
+        assert_eq!(splitter.combine(&decoded[..threshold]).unwrap(), key, "{:?}", splitter);
+    }
+}
+
+#[test]
+fn a_threshold_of_one_is_rejected_when_splitting() {
+    let mut rng = ChaChaRng::from_seed([7u8; 32]);
+    let result = ShamirSplit.split(b"copies, not shares", 3, 1, &mut rng);
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+}
+
+#[test]
+fn payloads_shorter_than_the_splitter_deals_are_rejected_before_solving() {
+    let (_, shards) = shard(b"truncated puzzle", 3, 2, [8u8; 32]);
+    let mut truncated = shards[..2].to_vec();
+    truncated[1].payload.truncate(4);
+
+    let result = TimelockKeySharding::new(2, 2).reconstruct_bytes(&truncated);
+    assert!(matches!(result, Err(TimelockError::CorruptedShard { index: 1 })), "{:?}", result);
+}
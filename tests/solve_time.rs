@@ -0,0 +1,15 @@
+use std::time::Duration;
+use timelock_key_sharing::LCS35;
+
+#[test]
+fn solve_time_scales_with_the_squaring_rate() {
+    let puzzle = LCS35::for_unlock(20);
+    assert_eq!(puzzle.estimate_solve_time(1 << 20), Duration::from_secs(1));
+    assert_eq!(puzzle.estimate_solve_time(1 << 10), Duration::from_secs(1 << 10));
+}
+
+#[test]
+fn unrepresentable_solve_times_saturate() {
+    assert_eq!(LCS35::for_unlock(64).estimate_solve_time(1), Duration::MAX);
+    assert_eq!(LCS35::for_unlock(20).estimate_solve_time(0), Duration::MAX);
+}
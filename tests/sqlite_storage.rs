@@ -0,0 +1,96 @@
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+use common::shard;
+use std::fs;
+use std::path::PathBuf;
+use timelock_key_sharing::{MaturityClock, SqliteBackend, StorageBackend, TimelockError};
+
+fn database(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("schrodinger-sqlite-{}-{}.db", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn stored_shards_export_unchanged() {
+    let (_, mut shards) = shard(b"sqlite backend", 3, 2, [43; 32]);
+    shards[1].maturity_clock = Some(MaturityClock::new(1_900_000_000));
+    let path = database("round-trip");
+    let backend = SqliteBackend::open(&path).unwrap();
+
+    backend.store_all(&shards[1..]).unwrap();
+    backend.store_shard(&shards[0]).unwrap();
+    assert_eq!(backend.list_shards().unwrap(), [1, 2, 3]);
+    assert_eq!(backend.load_shard(2).unwrap(), shards[1]);
+    drop(backend);
+
+    assert_eq!(SqliteBackend::open(&path).unwrap().export_all().unwrap(), shards);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_missing_shard_is_a_storage_error() {
+    let path = database("missing");
+    let backend = SqliteBackend::open(&path).unwrap();
+    let result = backend.load_shard(7);
+    assert!(matches!(&result, Err(TimelockError::Storage(message)) if message.contains("7")), "{:?}", result);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_adds_release_at_to_an_older_database() {
+    let (_, shards) = shard(b"sqlite backend", 3, 2, [44; 32]);
+    let path = database("migrated");
+    rusqlite::Connection::open(&path).unwrap().execute_batch(
+        "CREATE TABLE shards (
+            \"index\" INTEGER PRIMARY KEY,
+            version INTEGER NOT NULL,
+            total INTEGER NOT NULL,
+            threshold INTEGER NOT NULL,
+            payload BLOB NOT NULL,
+            checksum INTEGER NOT NULL,
+            mac BLOB,
+            created_at INTEGER NOT NULL
+        )",
+    ).unwrap();
+
+    let backend = SqliteBackend::open(&path).unwrap();
+    backend.store_shard(&shards[0]).unwrap();
+    assert_eq!(backend.load_shard(1).unwrap(), shards[0]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_adds_checksums_to_a_database_from_before_them() {
+    let (_, shards) = shard(b"sqlite backend", 3, 2, [45; 32]);
+    let path = database("checksums");
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE shards (
+            \"index\" INTEGER PRIMARY KEY,
+            version INTEGER NOT NULL,
+            total INTEGER NOT NULL,
+            threshold INTEGER NOT NULL,
+            payload BLOB NOT NULL,
+            mac BLOB,
+            created_at INTEGER NOT NULL
+        )",
+    ).unwrap();
+    for shard in &shards {
+        conn.execute(
+            "INSERT INTO shards (\"index\", version, total, threshold, payload, mac, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
+            rusqlite::params![shard.index, shard.version, shard.total, shard.threshold, shard.payload,
+                              shard.mac.as_ref().map(|mac| mac.as_slice())],
+        ).unwrap();
+    }
+    drop(conn);
+
+    let backend = SqliteBackend::open(&path).unwrap();
+    let exported = backend.export_all().unwrap();
+    assert_eq!(exported, shards);
+    assert!(exported.iter().all(|shard| shard.verify_checksum()));
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,52 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::sss::{self, SssError, MAX_SECRET_LEN};
+
+#[test]
+fn any_threshold_of_shares_recovers_the_secret() {
+    let secret = b"firmware unlock secret";
+    let mut rng = ChaChaRng::from_seed([1u8; 32]);
+    let shares = sss::split::<5, _>(secret, 5, 3, &mut rng).unwrap();
+
+    assert_eq!(sss::combine(&shares[..3]).unwrap().as_slice(), secret);
+    assert_eq!(sss::combine(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap().as_slice(), secret);
+    assert_ne!(sss::combine(&shares[..2]).unwrap().as_slice(), secret);
+}
+
+#[test]
+fn invalid_parameters_and_shares_are_rejected() {
+    let mut rng = ChaChaRng::from_seed([2u8; 32]);
+    assert_eq!(sss::split::<5, _>(&[0u8; MAX_SECRET_LEN + 1], 5, 3, &mut rng).unwrap_err(),
+               SssError::SecretTooLong { len: MAX_SECRET_LEN + 1, max: MAX_SECRET_LEN });
+    assert_eq!(sss::split::<4, _>(b"secret", 5, 3, &mut rng).unwrap_err(),
+               SssError::InvalidShareCount { requested: 5, max: 4 });
+    assert_eq!(sss::split::<5, _>(b"secret", 5, 1, &mut rng).unwrap_err(),
+               SssError::InvalidThreshold { threshold: 1, num_shares: 5 });
+
+    let shares = sss::split::<3, _>(b"secret", 3, 2, &mut rng).unwrap();
+    assert_eq!(sss::combine(&[]).unwrap_err(), SssError::NoShares);
+    assert_eq!(sss::combine(&[shares[1].clone(), shares[1].clone()]).unwrap_err(), SssError::DuplicateShare { x: 2 });
+
+    let mut zero = shares[0].clone();
+    zero.x = 0;
+    assert_eq!(sss::combine(&[shares[1].clone(), zero]).unwrap_err(), SssError::ZeroCoordinate { index: 1 });
+
+    let mut short = shares[2].clone();
+    short.y.pop();
+    assert_eq!(sss::combine(&[shares[0].clone(), short]).unwrap_err(),
+               SssError::LengthMismatch { index: 1, len: 5, expected: 6 });
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn shamir_split_deals_the_same_shares_as_the_core() {
+    use timelock_key_sharing::{SecretSplitter, ShamirSplit};
+
+    let secret = b"one implementation";
+    let shares = sss::split::<4, _>(secret, 4, 3, &mut ChaChaRng::from_seed([3u8; 32])).unwrap();
+    let shards = ShamirSplit.split(secret, 4, 3, &mut ChaChaRng::from_seed([3u8; 32])).unwrap();
+    for (share, shard) in shares.iter().zip(&shards) {
+        assert_eq!(shard.payload[0], share.x);
+        assert_eq!(&shard.payload[1..], share.y.as_slice());
+    }
+}
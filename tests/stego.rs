@@ -0,0 +1,38 @@
+#![cfg(feature = "stego")]
+
+mod common;
+
+use common::shard;
+use image::{ImageFormat, Rgba, RgbaImage};
+use std::io::Cursor;
+use timelock_key_sharing::{embed_in_png, extract_from_png, TimelockError};
+
+fn cover(width: u32, height: u32) -> Vec<u8> {
+    let image = RgbaImage::from_fn(width, height, |x, y| Rgba([(x * 7 + y) as u8, (y * 3) as u8, 90, 255]));
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).unwrap();
+    png
+}
+
+#[test]
+fn an_embedded_shard_is_extracted_unchanged() {
+    let (_, shards) = shard(b"hidden in the red channel", 3, 2, [51; 32]);
+    let png = embed_in_png(&shards[0], &cover(64, 64)).unwrap();
+    assert_eq!(extract_from_png(&png).unwrap(), shards[0]);
+}
+
+#[test]
+fn an_image_too_small_for_the_shard_is_rejected() {
+    let (_, shards) = shard(b"hidden in the red channel", 3, 2, [52; 32]);
+    let result = embed_in_png(&shards[0], &cover(8, 8));
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+
+    // 16 pixels cannot even hold the 32-bit length prefix
+    let tiny = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+    let mut png = Vec::new();
+    tiny.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).unwrap();
+    let result = extract_from_png(&png);
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("too small")), "{:?}", result);
+
+    assert!(matches!(extract_from_png(b"not a png"), Err(TimelockError::InvalidShardFormat(_))));
+}
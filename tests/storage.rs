@@ -0,0 +1,44 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::shard;
+use std::fs;
+use timelock_key_sharing::{FileSystemBackend, StorageBackend, TimelockError};
+
+fn backend(name: &str) -> FileSystemBackend {
+    let root = std::env::temp_dir().join(format!("schrodinger-storage-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    FileSystemBackend::new(root)
+}
+
+#[test]
+fn stored_shards_load_back_unchanged() {
+    let (_, shards) = shard(b"filesystem backend", 3, 2, [41; 32]);
+    let backend = backend("round-trip");
+    for shard in shards.iter().rev() {
+        backend.store_shard(shard).unwrap();
+    }
+    fs::write(backend.root().join("notes.txt"), "not a shard").unwrap();
+
+    assert_eq!(backend.list_shards().unwrap(), [1, 2, 3]);
+    for shard in &shards {
+        assert_eq!(backend.load_shard(shard.index).unwrap(), *shard);
+    }
+
+    fs::remove_dir_all(backend.root()).unwrap();
+}
+
+#[test]
+fn a_shard_file_under_the_wrong_name_is_rejected() {
+    let (_, shards) = shard(b"filesystem backend", 3, 2, [42; 32]);
+    let backend = backend("renamed");
+    backend.store_shard(&shards[0]).unwrap();
+    fs::rename(backend.root().join("shard_001.json"), backend.root().join("shard_002.json")).unwrap();
+
+    let result = backend.load_shard(2);
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("holds shard 1")), "{:?}", result);
+    assert!(matches!(backend.load_shard(1), Err(TimelockError::Io(_))));
+
+    fs::remove_dir_all(backend.root()).unwrap();
+}
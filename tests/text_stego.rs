@@ -0,0 +1,32 @@
+mod common;
+
+use common::shard;
+use timelock_key_sharing::{extract_from_text, hide_in_text, TimelockError};
+
+fn cover(words: usize) -> String {
+    vec!["the quick brown fox jumps over the lazy dog"; words.div_ceil(9)].join(" ")
+}
+
+#[test]
+fn a_hidden_shard_is_extracted_and_the_words_are_untouched() {
+    let (_, shards) = shard(b"between the words", 3, 2, [131; 32]);
+    let cover = cover(2000);
+
+    let stego = hide_in_text(&shards[0], &cover).unwrap();
+    assert_eq!(stego.split([' ', '\u{a0}']).collect::<Vec<_>>(), cover.split(' ').collect::<Vec<_>>());
+    assert_eq!(extract_from_text(&stego).unwrap(), shards[0]);
+}
+
+#[test]
+fn too_few_spaces_are_rejected() {
+    let (_, shards) = shard(b"between the words", 3, 2, [132; 32]);
+    let result = hide_in_text(&shards[0], &cover(100));
+    assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", result);
+
+    let stego = hide_in_text(&shards[0], &cover(2000)).unwrap();
+    // One space short of the bits the shard needs
+    let needed = (4 + shards[0].to_bytes().len()) * 8;
+    let cut: String = stego.split_inclusive([' ', '\u{a0}']).take(needed - 1).collect();
+    let result = extract_from_text(&cut);
+    assert!(matches!(&result, Err(TimelockError::InvalidShardFormat(message)) if message.contains("too short")), "{:?}", result);
+}
@@ -0,0 +1,34 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{FeldmanVSS, TimelockError};
+
+#[test]
+fn verified_shards_reconstruct_the_key() {
+    let key = b"feldman commitments over the modp group";
+    let (shards, commitments) = FeldmanVSS::commit_shards(key, 4, 3, &mut ChaChaRng::from_seed([11; 32])).unwrap();
+    assert_eq!(commitments.len(), 3);
+
+    assert!(shards.iter().all(|shard| FeldmanVSS::verify_shard(shard, &commitments)));
+    assert_eq!(FeldmanVSS::reconstruct(&shards[1..]).unwrap(), key);
+}
+
+#[test]
+fn a_tampered_share_fails_verification() {
+    let (mut shards, commitments) = FeldmanVSS::commit_shards(b"tamper", 3, 2, &mut ChaChaRng::from_seed([12; 32])).unwrap();
+    let last = shards[0].payload.len() - 1;
+    shards[0].payload[last] ^= 1;
+
+    assert!(!FeldmanVSS::verify_shard(&shards[0], &commitments));
+    assert!(FeldmanVSS::verify_shard(&shards[1], &commitments));
+}
+
+#[test]
+fn split_parameters_are_checked() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for (num_shards, threshold) in [(1, 1), (3, 1), (3, 4)] {
+        let result = FeldmanVSS::commit_shards(b"key", num_shards, threshold, &mut rng);
+        assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{}-of-{}", threshold, num_shards);
+    }
+    let result = FeldmanVSS::commit_shards(b"key", 256, 2, &mut rng);
+    assert!(matches!(result, Err(TimelockError::TooManyShards { requested: 256, .. })), "{:?}", result.map(|_| ()));
+}
@@ -0,0 +1,36 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use timelock_key_sharing::{Shard, TimelockError, WeightedSharding};
+
+// Custodian 1 carries three votes, custodians 2 and 3 one each
+const WEIGHTS: [(u8, usize); 3] = [(1, 3), (2, 1), (3, 1)];
+
+#[test]
+fn custodians_whose_weights_reach_the_threshold_recover_the_key() {
+    let key = b"weighted custodian key";
+    let shards = WeightedSharding::shard_weighted(key, &WEIGHTS, 4, &mut ChaChaRng::from_seed([71; 32])).unwrap();
+    assert_eq!(shards.iter().map(|shard| shard.index).collect::<Vec<_>>(), [1, 2, 3]);
+    assert!(shards.iter().all(|shard| shard.total == 5));
+    let shards: Vec<Shard> = shards.iter().map(|shard| Shard::from_bytes(&shard.to_bytes()).unwrap()).collect();
+
+    assert_eq!(WeightedSharding::reconstruct_weighted(&shards[..2]).unwrap(), key);
+    assert_eq!(WeightedSharding::reconstruct_weighted(&[shards[2].clone(), shards[0].clone()]).unwrap(), key);
+
+    let result = WeightedSharding::reconstruct_weighted(&shards[1..]);
+    assert!(matches!(result, Err(TimelockError::InsufficientShards { provided: 2, required: 4 })), "{:?}", result);
+}
+
+#[test]
+fn invalid_weights_and_repeated_custodians_are_rejected() {
+    let mut rng = ChaChaRng::from_seed([72; 32]);
+    for (weights, threshold) in [(&[(1, 3)][..], 2), (&[(1, 1), (2, 0)][..], 2), (&[(1, 1), (2, 1)][..], 1), (&[(1, 1), (2, 1)][..], 3)] {
+        let result = WeightedSharding::shard_weighted(b"key", weights, threshold, &mut rng);
+        assert!(matches!(result, Err(TimelockError::InvalidConfiguration(_))), "{:?}", weights);
+    }
+    let result = WeightedSharding::shard_weighted(b"key", &[(4, 1), (4, 2)], 2, &mut rng);
+    assert!(matches!(result, Err(TimelockError::DuplicateShard { index: 4 })), "{:?}", result);
+
+    let shards = WeightedSharding::shard_weighted(b"key", &WEIGHTS, 4, &mut rng).unwrap();
+    let result = WeightedSharding::reconstruct_weighted(&[shards[1].clone(), shards[1].clone()]);
+    assert!(matches!(result, Err(TimelockError::DuplicateShard { index: 2 })), "{:?}", result);
+}
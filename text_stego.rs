@@ -0,0 +1,59 @@
+// Hiding a shard in the spaces of a plain text
+//
+// Every space in the cover text carries one bit: an ordinary space (U+0020) is
+// 0 and a no-break space (U+00A0) is 1. As in `stego`, the message is the shard
+// length (u32 LE) followed by `Shard::to_bytes`, each byte least significant bit
+// first. The two spaces render alike, but anything that normalizes whitespace,
+// such as many word processors and web forms, destroys the shard.
+
+use crate::error::TimelockError;
+use crate::shard::Shard;
+
+const LENGTH_PREFIX_LEN: usize = 4;
+
+const ZERO: char = ' ';
+const ONE: char = '\u{a0}';
+
+pub fn hide_in_text(shard: &Shard, cover_text: &str) -> Result<String, TimelockError> {
+    let shard_bytes = shard.to_bytes();
+    let mut message = (shard_bytes.len() as u32).to_le_bytes().to_vec();
+    message.extend_from_slice(&shard_bytes);
+
+    let capacity = cover_text.chars().filter(|&c| is_carrier(c)).count();
+    if message.len() * 8 > capacity {
+        return Err(TimelockError::InvalidConfiguration(format!(
+            "cover text has {} spaces, but the shard needs {}", capacity, message.len() * 8)));
+    }
+
+    let mut bits = message.iter().flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1));
+    Ok(cover_text.chars()
+        .map(|c| match is_carrier(c).then(|| bits.next()).flatten() {
+            Some(1) => ONE,
+            Some(_) => ZERO,
+            None => c,
+        })
+        .collect())
+}
+
+pub fn extract_from_text(stego_text: &str) -> Result<Shard, TimelockError> {
+    let mut bits = stego_text.chars().filter(|&c| is_carrier(c)).map(|c| (c == ONE) as u8);
+    let mut next_byte = || -> Option<u8> {
+        (0..8).try_fold(0u8, |byte, bit| Some(byte | bits.next()? << bit))
+    };
+    let truncated = || TimelockError::InvalidShardFormat("text is too short to hold the hidden shard".to_string());
+
+    let mut prefix = [0u8; LENGTH_PREFIX_LEN];
+    for byte in &mut prefix {
+        *byte = next_byte().ok_or_else(truncated)?;
+    }
+    let len = u32::from_le_bytes(prefix) as usize;
+
+    let shard_bytes = (0..len)
+        .map(|_| next_byte().ok_or_else(truncated))
+        .collect::<Result<Vec<u8>, _>>()?;
+    Shard::from_bytes(&shard_bytes)
+}
+
+fn is_carrier(c: char) -> bool {
+    c == ZERO || c == ONE
+}
@@ -0,0 +1,56 @@
+// Commitment to a shard set's threshold, against later tampering
+//
+// The coordinator publishes SHA256(threshold || salt) at the ceremony and keeps
+// the salt. Revealing the salt at reconstruction lets every party check that
+// the threshold carried by the shards and capsule is the one committed to, not
+// one changed afterwards. It does not hide the threshold: every `Shard` and
+// `TimelockCapsule` stores it in the clear.
+
+use crate::secret_data::SecretData;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::{Choice, ConstantTimeEq};
+
+pub const THRESHOLD_SALT_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThresholdProof {
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::hex::Hex>"))]
+    pub commitment: [u8; 32],
+}
+
+impl SecretData for ThresholdProof {}
+
+impl ConstantTimeEq for ThresholdProof {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.commitment.as_slice().ct_eq(other.commitment.as_slice())
+    }
+}
+
+impl PartialEq for ThresholdProof {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl ThresholdProof {
+    pub fn commit(threshold: u8, salt: &[u8; THRESHOLD_SALT_LEN]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update([threshold]);
+        hasher.update(salt);
+        ThresholdProof { commitment: hasher.finalize().into() }
+    }
+
+    // Commit under a fresh salt from `rng`, returning the salt for the coordinator to keep
+    pub fn generate<R: RngCore + ?Sized>(threshold: u8, rng: &mut R) -> (Self, [u8; THRESHOLD_SALT_LEN]) {
+        let mut salt = [0u8; THRESHOLD_SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        (Self::commit(threshold, &salt), salt)
+    }
+
+    // Whether the revealed `threshold` and `salt` are the ones committed to
+    pub fn verify(&self, threshold: u8, salt: &[u8; THRESHOLD_SALT_LEN]) -> bool {
+        Self::commit(threshold, salt).ct_eq(self).into()
+    }
+}
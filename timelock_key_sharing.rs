@@ -1,288 +1,1721 @@
 // This is a conceptual implementation of the Timelock Key Sharding system
 // described in Project Schrödinger
+//
+// Without the default `std` feature only the GF(256) Shamir core in `sss` is
+// built, for embedded firmware. Every other optional feature builds on `std`.
 
-use rand::{RngCore, SeedableRng};
-use rand_chacha::ChaChaRng;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+use rand::RngCore;
+#[cfg(feature = "std")]
 use sha2::{Digest, Sha256};
-use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(all(feature = "std", unix))]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(feature = "age")]
+mod age_shard;
+#[cfg(feature = "std")]
+mod audit;
+#[cfg(feature = "parallel")]
+mod batch_entropy;
+#[cfg(feature = "serde")]
+mod benchmark;
+#[cfg(feature = "std")]
+mod blakley;
+#[cfg(feature = "bundle")]
+mod bundle;
+#[cfg(feature = "std")]
+mod cancellation;
+#[cfg(feature = "std")]
+mod capsule;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "std")]
+mod ceremony;
+#[cfg(feature = "std")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod coefficients;
+#[cfg(feature = "toml")]
+mod config;
+#[cfg(feature = "std")]
+mod cost;
+#[cfg(feature = "std")]
+mod custodian;
+#[cfg(feature = "std")]
+mod dead_man_switch;
+#[cfg(feature = "std")]
+mod decoy;
+#[cfg(feature = "std")]
+mod difficulty;
+#[cfg(feature = "std")]
+mod dns_txt;
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "std")]
+mod encrypted_shard;
+#[cfg(feature = "std")]
+pub mod entropy;
+#[cfg(feature = "std")]
+mod error;
+pub mod field;
+#[cfg(feature = "std")]
+mod format;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "std")]
+mod hash_algorithm;
+#[cfg(feature = "std")]
+mod hierarchical;
+#[cfg(feature = "std")]
+mod kdf;
+#[cfg(feature = "std")]
+mod key_ceremony;
+#[cfg(feature = "std")]
+mod nfc;
+#[cfg(feature = "std")]
+mod pem;
+#[cfg(feature = "std")]
+mod puzzle_params;
+#[cfg(feature = "qr")]
+mod qr;
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "std")]
+mod secret_splitter;
+#[cfg(feature = "std")]
+mod maturity;
+#[cfg(feature = "std")]
+mod merkle;
+#[cfg(feature = "std")]
+mod migration;
+#[cfg(feature = "std")]
+pub mod mnemonic;
+#[cfg(feature = "remote")]
+mod remote;
+#[cfg(feature = "std")]
+mod reshare;
+#[cfg(feature = "std")]
+mod secret_data;
+#[cfg(feature = "std")]
+mod shard;
+#[cfg(feature = "std")]
+mod shard_csv;
+#[cfg(feature = "server")]
+pub mod shard_server;
+#[cfg(feature = "std")]
+mod signed_shard;
+pub mod sss;
+#[cfg(feature = "stego")]
+pub mod stego;
+#[cfg(feature = "std")]
+mod storage;
+#[cfg(feature = "std")]
+mod text_stego;
+#[cfg(feature = "std")]
+mod threshold_proof;
+#[cfg(feature = "std")]
+mod vss;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "std")]
+mod weighted;
+
+#[cfg(feature = "age")]
+pub use age_shard::{decrypt_shard, encrypt_shard_to_recipients};
+#[cfg(feature = "std")]
+pub use audit::{AuditEvent, AuditLog, FileAuditLog};
+#[cfg(feature = "parallel")]
+pub use batch_entropy::batch_entropy_check;
+#[cfg(feature = "serde")]
+pub use benchmark::{default_benchmark_path, BenchmarkResult};
+#[cfg(feature = "std")]
+pub use blakley::{BlakleySplitter, DEFAULT_BLAKLEY_FIELD};
+#[cfg(feature = "bundle")]
+pub use bundle::{pack_shards, unpack_shards, BUNDLE_VERSION};
+#[cfg(feature = "std")]
+pub use cancellation::CancellationToken;
+#[cfg(feature = "std")]
+pub use capsule::TimelockCapsule;
+#[cfg(feature = "std")]
+pub use ceremony::{commit_shard, verify_commitment, verify_shard_commitment, ShardCommitment, SHARD_COMMITMENT_LEN};
+#[cfg(feature = "std")]
+pub use coefficients::SealedCoefficients;
+#[cfg(feature = "std")]
+pub use cost::{
+    estimate_local_reconstruction_cost, estimate_reconstruction_cost, estimate_reconstruction_cost_with_speedup,
+    local_squarings_per_second, ReconstructionCost,
+};
+#[cfg(feature = "std")]
+pub use custodian::{remap_shards, CustodianId};
+#[cfg(feature = "std")]
+pub use dead_man_switch::DeadManSwitch;
+#[cfg(feature = "std")]
+pub use decoy::DecoyShardGenerator;
+#[cfg(feature = "std")]
+pub use difficulty::{Difficulty, MAX_DIFFICULTY};
+#[cfg(feature = "std")]
+pub use encrypted_shard::EncryptedShard;
+#[cfg(feature = "std")]
+pub use entropy::{EntropyMeasure, SpectralTestResult};
+#[cfg(feature = "std")]
+pub use error::TimelockError;
+pub use field::{FiniteField, GFp, GF256};
+#[cfg(feature = "std")]
+pub use hash_algorithm::HashAlgorithm;
+#[cfg(feature = "std")]
+pub use hierarchical::{reconstruct_hierarchical, shard_hierarchical, HierarchicalSharding, LevelConfig};
+#[cfg(feature = "std")]
+pub use kdf::{derive_key_from_passphrase, Argon2Params, KeyDerivationRatchet};
+#[cfg(feature = "std")]
+pub use key_ceremony::{CeremonyRecord, KeyCeremony, ParticipantId, CEREMONY_KEY_LEN};
+#[cfg(feature = "std")]
+pub use maturity::MaturityClock;
+#[cfg(feature = "std")]
+pub use merkle::{build_shard_merkle_tree, MerkleProof, MerkleTree};
+#[cfg(feature = "std")]
+pub use migration::migrate_v1_to_v2;
+#[cfg(feature = "std")]
+pub use mnemonic::MnemonicShard;
+#[cfg(feature = "std")]
+pub use nfc::{reassemble_from_nfc, split_for_nfc};
+#[cfg(feature = "std")]
+pub use puzzle_params::PuzzleParams;
+#[cfg(feature = "std")]
+pub use rate_limit::{RateLimitState, ReconstructRateLimiter};
+#[cfg(feature = "std")]
+use secret_splitter::{check_shard_count, check_split_params};
+#[cfg(feature = "std")]
+pub use secret_splitter::{SecretSplitter, ShamirSplit, XorSplit};
+#[cfg(feature = "remote")]
+pub use remote::fetch_shard;
+#[cfg(feature = "std")]
+pub use reshare::{reshare, ReshareProtocol};
+#[cfg(feature = "std")]
+pub use secret_data::SecretData;
+#[cfg(feature = "std")]
+pub use shard::{verify_shard_set, Shard, ShardSetInfo, CURRENT_VERSION, MAX_SHARDS, MIN_PAYLOAD_LEN};
+#[cfg(feature = "std")]
+pub use shard_csv::{read_shard_csv, write_shard_csv, CSV_HEADER};
+#[cfg(feature = "std")]
+pub use signed_shard::{sign_shard, verify_signed_shard, SignedShard, SIGNATURE_LEN};
+#[cfg(feature = "stego")]
+pub use stego::{embed_in_png, extract_from_png};
+#[cfg(feature = "std")]
+pub use storage::StorageBackend;
+#[cfg(feature = "std")]
+pub use text_stego::{extract_from_text, hide_in_text};
+#[cfg(feature = "serde")]
+pub use storage::FileSystemBackend;
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteBackend;
+#[cfg(feature = "std")]
+pub use threshold_proof::{ThresholdProof, THRESHOLD_SALT_LEN};
+#[cfg(feature = "std")]
+pub use vss::{Commitment, FeldmanVSS};
+#[cfg(feature = "wasm")]
+pub use wasm::{reconstruct_key_wasm, shard_key_wasm};
+#[cfg(feature = "std")]
+pub use weighted::WeightedSharding;
 
 // Mock module to represent the Silurian LCS35 timelock puzzle
 // In a real implementation, this would be a properly implemented cryptographic library
+#[cfg(feature = "std")]
 mod silurian_puzzle {
-    use rand::RngCore;
+    use crate::cancellation::CancellationToken;
+    use crate::checkpoint::{self, Checkpoint};
+    use crate::cost;
+    use crate::error::TimelockError;
+    use crate::difficulty::{Difficulty, MAX_DIFFICULTY};
+    use crate::field::{self, GF256};
+    use crate::hash_algorithm::HashAlgorithm;
+    use crate::puzzle_params::PuzzleParams;
+    use crate::secret_splitter::{check_split_params, combine_bytes, SecretSplitter};
+    use crate::shard::Shard;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::{One, Zero};
+    use rand::{Rng, RngCore};
+    #[cfg(feature = "parallel")]
+    use rayon::prelude::*;
     use sha2::{Digest, Sha256};
+    use std::collections::HashSet;
     use std::fmt;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+    use subtle::ConstantTimeEq;
+    use zeroize::Zeroizing;
+
+    // Modulus size used by `LCS35::new`. The original LCS35 puzzle uses 2048 bits.
+    pub const DEFAULT_MODULUS_BITS: usize = 512;
+
+    // Number of squarings `LCS35::calibrate` times to measure the local squaring rate
+    const CALIBRATION_SQUARINGS: u64 = 1_000_000;
+
+    // Squarings between calls to an `unlock_with_progress` callback unless overridden
+    pub const DEFAULT_PROGRESS_INTERVAL: u64 = 100_000;
+
+    // Squarings between checkpoint writes in `unlock_resumable` unless overridden
+    pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 10_000_000;
+
+    // Number of Miller-Rabin rounds used when generating the RSA primes
+    const MILLER_RABIN_ROUNDS: usize = 32;
+
+    // Set in the payload's hash byte when a solution commitment follows the base
+    const COMMITMENT_FLAG: u8 = 0x80;
+
+    const COMMITMENT_LEN: usize = 32;
+
+    const SMALL_PRIMES: [u32; 25] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41,
+        43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    ];
 
     pub struct LCS35 {
         difficulty: u32,
         iterations: u64,
+        modulus: BigUint,
+        // phi(n) is the puzzle creator's trapdoor: it lets `shard` compute
+        // 2^(2^t) mod n without performing the t sequential squarings
+        totient: BigUint,
+        // Squarings between progress callbacks in `unlock_with_progress`
+        progress_interval: u64,
+        // Squarings between checkpoint writes in `unlock_resumable`
+        checkpoint_interval: u64,
+        // Hash used by `shard` to derive keystreams; `unlock` reads it from each shard
+        hash_algorithm: HashAlgorithm,
     }
 
     impl LCS35 {
         pub fn new(difficulty: u32) -> Self {
-            // Calculate iterations based on difficulty
-            // For a 35-year timelock, difficulty would be very high
-            let iterations = 2u64.pow(difficulty);
-            
+            Self::generate_rsa_modulus(difficulty, DEFAULT_MODULUS_BITS)
+        }
+
+        // As `new`, deriving shard keystreams with `hash` instead of SHA-256
+        pub fn new_with_hash(difficulty: u32, hash: HashAlgorithm) -> Self {
+            Self::new(difficulty).with_hash_algorithm(hash)
+        }
+
+        // Create a puzzle over a fresh RSA modulus n = p * q of the given bit length
+        pub fn generate_rsa_modulus(difficulty: u32, bits: usize) -> Self {
+            Self::generate_rsa_modulus_with_rng(difficulty, bits, &mut rand::thread_rng())
+        }
+
+        // As `generate_rsa_modulus`, drawing the primes from the supplied RNG
+        pub fn generate_rsa_modulus_with_rng<R: RngCore + ?Sized>(difficulty: u32, bits: usize, rng: &mut R) -> Self {
+            if bits < 16 {
+                panic!("RSA modulus must be at least 16 bits");
+            }
+
+            // 2^difficulty squarings, saturating above `MAX_DIFFICULTY`; the
+            // builder rejects such difficulties before they get here
+            let iterations = Difficulty(difficulty).iterations();
+
+            let p = generate_prime(bits / 2, rng);
+            let mut q = generate_prime(bits - bits / 2, rng);
+            while q == p {
+                q = generate_prime(bits - bits / 2, rng);
+            }
+
+            let one = BigUint::one();
+            let totient = (&p - &one) * (&q - &one);
+
             LCS35 {
                 difficulty,
                 iterations,
+                modulus: p * q,
+                totient,
+                progress_interval: DEFAULT_PROGRESS_INTERVAL,
+                checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+                hash_algorithm: HashAlgorithm::default(),
             }
         }
 
-        pub fn shard(&self, key: &[u8], num_shards: usize) -> Vec<Vec<u8>> {
-            if num_shards < 2 {
-                panic!("Number of shards must be at least 2");
+        // A puzzle for solving existing shards only. Each shard carries its own
+        // modulus, base and hash, so no RSA modulus is generated; `shard` and
+        // `issue_shard` fail for lack of a trapdoor.
+        pub fn for_unlock(difficulty: u32) -> Self {
+            LCS35 {
+                difficulty,
+                iterations: Difficulty(difficulty).iterations(),
+                modulus: BigUint::zero(),
+                totient: BigUint::zero(),
+                progress_interval: DEFAULT_PROGRESS_INTERVAL,
+                checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+                hash_algorithm: HashAlgorithm::default(),
             }
+        }
 
-            // Create shards using Shamir's Secret Sharing scheme (simplified)
-            let mut shards = Vec::with_capacity(num_shards);
-            
-            // Generate coefficients for polynomial
-            let mut coefficients = Vec::with_capacity(num_shards - 1);
-            for _ in 0..num_shards - 1 {
-                let mut hasher = Sha256::new();
-                hasher.update(&key);
-                hasher.update(&self.iterations.to_le_bytes());
-                coefficients.push(hasher.finalize().to_vec());
+        // Set how many squarings pass between progress callbacks; zero is treated as one
+        pub fn with_progress_interval(mut self, interval: u64) -> Self {
+            self.progress_interval = interval.max(1);
+            self
+        }
+
+        // Derive shard keystreams with `hash` instead of SHA-256
+        pub fn with_hash_algorithm(mut self, hash: HashAlgorithm) -> Self {
+            self.hash_algorithm = hash;
+            self
+        }
+
+        // Set how many squarings pass between checkpoint writes; zero is treated as one
+        pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+            self.checkpoint_interval = interval.max(1);
+            self
+        }
+
+        // Public parameters of this puzzle. The base differs from shard to shard,
+        // so `initial_value_hex` is left empty.
+        pub fn export_params(&self) -> PuzzleParams {
+            PuzzleParams {
+                difficulty: self.difficulty,
+                iterations: self.iterations,
+                modulus_hex: Some(hex::encode(self.modulus.to_bytes_be())),
+                initial_value_hex: None,
+            }
+        }
+
+        // A puzzle from public parameters, e.g. a capsule's. Without phi(n) it can
+        // solve and check shards but not create them: `shard` and `issue_shard`
+        // fail. `initial_value_hex` is not needed, as each shard carries its base.
+        pub fn from_params(params: &PuzzleParams) -> Result<Self, TimelockError> {
+            if params.iterations != Difficulty(params.difficulty).iterations() {
+                return Err(TimelockError::InvalidConfiguration(format!(
+                    "{} iterations do not match difficulty {}", params.iterations, params.difficulty)));
+            }
+            let modulus = match &params.modulus_hex {
+                Some(modulus_hex) => BigUint::from_bytes_be(&hex::decode(modulus_hex)?),
+                None => BigUint::zero(),
+            };
+
+            let mut puzzle = Self::for_unlock(params.difficulty);
+            puzzle.modulus = modulus;
+            Ok(puzzle)
+        }
+
+        // Estimated wall-clock time to solve one shard's puzzle at the given squaring rate.
+        // Reconstruction solves `threshold` puzzles, so the total is proportionally longer.
+        // Estimates beyond `Duration::MAX` saturate there.
+        pub fn estimate_solve_time(&self, squarings_per_second: u64) -> Duration {
+            if squarings_per_second == 0 {
+                return Duration::MAX;
             }
+            Duration::try_from_secs_f64(self.iterations as f64 / squarings_per_second as f64).unwrap_or(Duration::MAX)
+        }
+
+        // Benchmark sequential squaring on this machine and return the difficulty
+        // whose 2^difficulty squarings take at least `target_duration`. The measured
+        // rate becomes the local benchmark behind `estimate_local_reconstruction_cost`.
+        pub fn calibrate(target_duration: Duration) -> u32 {
+            let squarings_per_second = measure_squarings_per_second(CALIBRATION_SQUARINGS);
+            cost::record_local_benchmark(squarings_per_second);
+            let target_squarings = target_duration.as_secs_f64() * squarings_per_second as f64;
+
+            // iterations = 2^difficulty must still fit in a u64
+            (target_squarings.log2().ceil().max(1.0) as u32).min(MAX_DIFFICULTY)
+        }
+
+        // Square for `duration` and return the rate achieved, recording it as the
+        // local benchmark like `calibrate` does
+        pub fn benchmark(duration: Duration) -> u64 {
+            let squarings_per_second = measure_squarings_for(duration);
+            cost::record_local_benchmark(squarings_per_second);
+            squarings_per_second
+        }
+
+        // Split `key` into `num_shards` timelocked shards, any `threshold` of which
+        // reconstruct it. Polynomial coefficients and puzzle bases come from `rng`.
+        // Fails with `TooManyShards` above `MAX_SHARDS`, and for fewer than two
+        // shards or a threshold outside 1..=num_shards.
+        pub fn shard<R: RngCore + ?Sized>(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut R)
+            -> Result<Vec<Shard>, TimelockError>
+        {
+            Ok(self.shard_with_coefficients(key, num_shards, threshold, rng)?.0)
+        }
+        
+        // As `shard`, also returning the polynomial coefficients so the dealer can
+        // issue further shards later with `issue_shard`. They are laid out as
+        // `threshold` coefficients per key byte in ascending degree order, and the
+        // constant terms are the key itself.
+        pub fn shard_with_coefficients<R: RngCore + ?Sized>(&self, key: &[u8], num_shards: usize, threshold: usize,
+                                                           rng: &mut R)
+            -> Result<(Vec<Shard>, Zeroizing<Vec<u8>>), TimelockError>
+        {
+            check_split_params(num_shards, threshold)?;
+            self.check_trapdoor()?;
+
+            // Create shards using Shamir's Secret Sharing scheme over GF(256):
+            // every key byte gets its own polynomial of degree threshold - 1,
+            // with the key byte itself as the constant term
+            let mut coefficients = Zeroizing::new(vec![0u8; key.len() * threshold]);
+            for (polynomial, &secret_byte) in coefficients.chunks_mut(threshold).zip(key) {
+                polynomial[0] = secret_byte;
+                rng.fill_bytes(&mut polynomial[1..]);
+            }
+            
+            // Draw every shard's puzzle base up front so the output for a given
+            // RNG is the same whether or not shards are generated in parallel
+            let bases: Vec<BigUint> = (0..num_shards)
+                .map(|_| self.random_base(rng))
+                .collect();
             
-            // Generate shards
-            for i in 1..=num_shards {
+            let exponent = self.trapdoor_exponent();
+            
+            // Tag every shard so tampering is caught before reconstruction
+            let mac_key = Zeroizing::new(Shard::derive_mac_key(key));
+            
+            // Generate shards; each one is independent of the others
+            let build_shard = |i: usize| {
                 let x_value = i as u8;
-                let mut shard = Vec::new();
-                shard.push(x_value);
-                
-                // Apply timelock puzzle to each shard
-                let mut hasher = Sha256::new();
-                hasher.update(&key);
-                hasher.update(&[x_value]);
+                let mut shard = Shard::new(x_value, num_shards as u8, threshold as u8,
+                    self.lock_payload(x_value, &bases[i - 1], &exponent, &coefficients, threshold));
+                shard.mac = Some(shard.compute_mac(mac_key.as_slice()));
+                shard
+            };
+            
+            #[cfg(feature = "parallel")]
+            let shards = (1..=num_shards).into_par_iter().map(build_shard).collect();
+            #[cfg(not(feature = "parallel"))]
+            let shards = (1..=num_shards).map(build_shard).collect();
+            
+            Ok((shards, coefficients))
+        }
+        
+        // Issue one more shard at x-coordinate `x` from coefficients returned by
+        // `shard_with_coefficients`, locked behind this puzzle. `total` and
+        // `threshold` are copied into the shard so it matches its set.
+        pub fn issue_shard<R: RngCore + ?Sized>(&self, x: u8, total: u8, threshold: u8, coefficients: &[u8],
+                                               rng: &mut R) -> Result<Shard, TimelockError> {
+            if x == 0 {
+                return Err(TimelockError::InvalidConfiguration(
+                    "x-coordinate 0 would reveal the key".to_string()));
+            }
+            if threshold == 0 || coefficients.is_empty() || !coefficients.len().is_multiple_of(threshold as usize) {
+                return Err(TimelockError::InvalidConfiguration(format!(
+                    "{} coefficients do not form degree-{} polynomials",
+                    coefficients.len(), threshold.saturating_sub(1))));
+            }
+            self.check_trapdoor()?;
+            
+            let threshold = threshold as usize;
+            let key: Zeroizing<Vec<u8>> = Zeroizing::new(coefficients.iter().step_by(threshold).copied().collect());
+            let mac_key = Zeroizing::new(Shard::derive_mac_key(&key));
+            
+            let base = self.random_base(rng);
+            let mut shard = Shard::new(x, total, threshold as u8,
+                self.lock_payload(x, &base, &self.trapdoor_exponent(), coefficients, threshold));
+            shard.mac = Some(shard.compute_mac(mac_key.as_slice()));
+            Ok(shard)
+        }
+        
+        // Puzzles made by `from_params` have no trapdoor to lock shards with
+        fn check_trapdoor(&self) -> Result<(), TimelockError> {
+            if self.totient.is_zero() {
+                return Err(TimelockError::PuzzleError(
+                    "puzzle was imported from public parameters and cannot create shards".to_string()));
+            }
+            Ok(())
+        }
+        
+        fn random_base<R: RngCore + ?Sized>(&self, rng: &mut R) -> BigUint {
+            rng.gen_biguint_range(&BigUint::from(2u32), &(&self.modulus - BigUint::one()))
+        }
+        
+        // With the trapdoor, 2^(2^t) mod n reduces to a single exponentiation
+        // by 2^t mod phi(n); solvers without phi(n) must square t times
+        fn trapdoor_exponent(&self) -> BigUint {
+            BigUint::from(2u32).modpow(&BigUint::from(self.iterations), &self.totient)
+        }
+        
+        // Evaluate every byte's polynomial at `x` and lock the results behind
+        // the puzzle with base `base`
+        fn lock_payload(&self, x: u8, base: &BigUint, exponent: &BigUint, coefficients: &[u8], threshold: usize) -> Vec<u8> {
+            let key_len = coefficients.len() / threshold;
+            let solution = base.modpow(exponent, &self.modulus);
+            let keystream = derive_keystream(self.hash_algorithm, &solution, key_len);
+            let modulus_bytes = self.modulus.to_bytes_le();
+            let base_bytes = base.to_bytes_le();
+            
+            // Payload layout: [x, hash | COMMITMENT_FLAG, n_len (u16 LE), n, a_len (u16 LE), a,
+            // commitment (32 bytes), locked y_0, locked y_1, ...]. Shards issued before
+            // commitments existed leave the flag clear and omit the commitment.
+            let mut payload = Vec::with_capacity(6 + modulus_bytes.len() + base_bytes.len() + COMMITMENT_LEN + key_len);
+            payload.push(x);
+            payload.push(self.hash_algorithm.id() | COMMITMENT_FLAG);
+            payload.extend_from_slice(&(modulus_bytes.len() as u16).to_le_bytes());
+            payload.extend_from_slice(&modulus_bytes);
+            payload.extend_from_slice(&(base_bytes.len() as u16).to_le_bytes());
+            payload.extend_from_slice(&base_bytes);
+            payload.extend_from_slice(&solution_commitment(&solution));
+            
+            // Evaluate each byte's polynomial at this shard's x-coordinate
+            let coefficients: Zeroizing<Vec<GF256>> = Zeroizing::new(coefficients.iter().map(|&c| GF256(c)).collect());
+            for (polynomial, mask) in coefficients.chunks(threshold).zip(keystream.iter()) {
+                payload.push(field::eval_poly(polynomial, GF256(x)).0 ^ mask);
+            }
+            payload
+        }
+        
+        // Check a solution to the puzzle in the shard payload `puzzle_input`, given as
+        // the little-endian bytes of a^(2^t) mod n, without performing any squarings.
+        // Uses the commitment stored in the payload, or failing that this puzzle's
+        // trapdoor if the payload was locked under its modulus.
+        pub fn verify_solution(&self, puzzle_input: &[u8], claimed_solution: &[u8]) -> bool {
+            let Some(locked) = LockedShard::parse(puzzle_input) else {
+                return false;
+            };
+            let claimed = BigUint::from_bytes_le(claimed_solution);
+            if claimed >= locked.modulus {
+                return false;
+            }
+            
+            match locked.commitment {
+                Some(commitment) => solution_commitment(&claimed).as_slice().ct_eq(commitment.as_slice()).into(),
+                None => !self.totient.is_zero()
+                    && locked.modulus == self.modulus
+                    && locked.base.modpow(&self.trapdoor_exponent(), &self.modulus) == claimed,
+            }
+        }
+        
+        // As `unlock`, but first checks every shard's MAC against `mac_key`
+        // (see `Shard::derive_mac_key`) and uses the threshold recorded in the shards
+        pub fn unlock_verified(&self, shards: &[Shard], mac_key: &[u8]) -> Result<Vec<u8>, TimelockError> {
+            if let Some(index) = shards.iter().position(|shard| !shard.verify_mac(mac_key)) {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            
+            let threshold = shards.first().map_or(1, |shard| shard.threshold as usize);
+            self.unlock(shards, threshold)
+        }
+        
+        pub fn unlock(&self, shards: &[Shard], threshold: usize) -> Result<Vec<u8>, TimelockError> {
+            self.unlock_with_progress(shards, threshold, |_, _| {}, &CancellationToken::new())
+        }
+        
+        // As `unlock`, calling `progress(completed, total)` every `progress_interval`
+        // squarings and once more when the last puzzle is solved. The counts cover
+        // the puzzles of all `threshold` shards together. `cancel` is checked on the
+        // same schedule and aborts with `TimelockError::Cancelled` once set.
+        pub fn unlock_with_progress<F>(&self, shards: &[Shard], threshold: usize, progress: F,
+                                       cancel: &CancellationToken)
+            -> Result<Vec<u8>, TimelockError>
+        where
+            F: Fn(u64, u64) + Send + Sync,
+        {
+            let locked_shards = Self::open_shards(shards, threshold)?;
+            
+            let total_iterations = self.iterations.saturating_mul(threshold as u64);
+            let mut completed = 0u64;
+            
+            let mut solutions = Vec::with_capacity(threshold);
+            for locked in &locked_shards {
+                if cancel.is_cancelled() {
+                    return Err(TimelockError::Cancelled);
+                }
                 
-                // Simulate iterative hashing (this would take years in real implementation)
-                // In a real implementation, this would use sequential squaring or similar
-                let mut hash = hasher.finalize().to_vec();
-                for _ in 0..10 {  // Just do a few iterations for demo purposes
-                    let mut hasher = Sha256::new();
-                    hasher.update(&hash);
-                    hash = hasher.finalize().to_vec();
+                // Solve the puzzle the slow way: t sequential squarings of the base
+                let mut solution = locked.base.clone();
+                for _ in 0..self.iterations {
+                    solution = &solution * &solution % &locked.modulus;
+                    completed += 1;
+                    if completed.is_multiple_of(self.progress_interval) {
+                        if cancel.is_cancelled() {
+                            return Err(TimelockError::Cancelled);
+                        }
+                        progress(completed, total_iterations);
+                    }
+                }
+                solutions.push(solution);
+            }
+            
+            if !completed.is_multiple_of(self.progress_interval) {
+                progress(completed, total_iterations);
+            }
+            
+            Self::combine(&locked_shards, &solutions)
+        }
+        
+        // As `unlock`, but the squaring state is saved to `checkpoint_path` every
+        // `checkpoint_interval` squarings and whenever a puzzle is finished. An
+        // existing checkpoint is resumed, and it is deleted once the key is recovered.
+        pub fn unlock_resumable(&self, shards: &[Shard], threshold: usize, checkpoint_path: &Path)
+            -> Result<Vec<u8>, TimelockError>
+        {
+            let locked_shards = Self::open_shards(shards, threshold)?;
+            
+            let mut state = Checkpoint::load(checkpoint_path)?
+                .unwrap_or_else(|| Checkpoint::new(self.iterations));
+            if state.iterations != self.iterations {
+                return Err(TimelockError::PuzzleError(format!(
+                    "checkpoint was written for {} squarings per puzzle, not {}",
+                    state.iterations, self.iterations)));
+            }
+            
+            let mut solutions = Vec::with_capacity(threshold);
+            for locked in &locked_shards {
+                let entry = state.entry_for(checkpoint::fingerprint(&locked.modulus, &locked.base), &locked.base);
+                if state.entries[entry].squarings > self.iterations {
+                    return Err(TimelockError::PuzzleError(
+                        "checkpoint is ahead of the puzzle it belongs to".to_string()));
                 }
                 
-                shard.extend_from_slice(&hash);
-                shards.push(shard);
+                while state.entries[entry].squarings < self.iterations {
+                    let current = &mut state.entries[entry];
+                    current.value = &current.value * &current.value % &locked.modulus;
+                    current.squarings += 1;
+                    if current.squarings.is_multiple_of(self.checkpoint_interval) {
+                        state.save(checkpoint_path)?;
+                    }
+                }
+                state.save(checkpoint_path)?;
+                solutions.push(state.entries[entry].value.clone());
             }
             
-            shards
+            let key = Self::combine(&locked_shards, &solutions)?;
+            std::fs::remove_file(checkpoint_path)?;
+            Ok(key)
         }
         
-        pub fn unlock(&self, shards: &[Vec<u8>], threshold: usize) -> Result<Vec<u8>, String> {
+        // Validate the shard set and parse the first `threshold` shards
+        fn open_shards(shards: &[Shard], threshold: usize) -> Result<Vec<LockedShard<'_>>, TimelockError> {
+            if threshold == 0 {
+                return Err(TimelockError::InvalidConfiguration("threshold must be at least 1".to_string()));
+            }
+            
             if shards.len() < threshold {
-                return Err("Not enough shards provided".to_string());
+                return Err(TimelockError::InsufficientShards {
+                    provided: shards.len(),
+                    required: threshold,
+                });
             }
             
-            // In a real implementation, this would:
-            // 1. Reconstruct the key using Lagrange interpolation
-            // 2. Verify the key using the timelock puzzle solution
+            // Repeated x-coordinates make the Lagrange basis degenerate, so reject
+            // them before spending any time on the puzzles
+            let mut seen = HashSet::with_capacity(shards.len());
+            for (index, shard) in shards.iter().enumerate() {
+                let x = *shard.payload.first().ok_or(TimelockError::CorruptedShard { index })?;
+                if !seen.insert(x) {
+                    return Err(TimelockError::DuplicateShard { index: x });
+                }
+            }
             
-            // For demo purposes, we'll just combine the shards with XOR
-            let mut key = vec![0u8; 32];
-            for shard in shards.iter().take(threshold) {
-                for (i, &byte) in shard.iter().skip(1).take(32).enumerate() {
-                    key[i] ^= byte;
+            let mut locked_shards = Vec::with_capacity(threshold);
+            for (index, shard) in shards.iter().take(threshold).enumerate() {
+                let locked = LockedShard::parse(&shard.payload)
+                    .ok_or(TimelockError::CorruptedShard { index })?;
+                if locked.x == 0 {
+                    return Err(TimelockError::CorruptedShard { index });
+                }
+                if locked.modulus <= BigUint::one() {
+                    return Err(TimelockError::PuzzleError(
+                        format!("shard {} has an invalid puzzle modulus", index)));
                 }
+                locked_shards.push(locked);
             }
             
-            Ok(key)
+            Ok(locked_shards)
+        }
+        
+        // Strip each shard's keystream using its puzzle solution and interpolate the key
+        fn combine(locked_shards: &[LockedShard<'_>], solutions: &[BigUint]) -> Result<Vec<u8>, TimelockError> {
+            let mut points = Vec::with_capacity(locked_shards.len());
+            for (index, (locked, solution)) in locked_shards.iter().zip(solutions).enumerate() {
+                // A solution that misses its commitment means the puzzle parameters were altered
+                let verified = locked.commitment.is_none_or(|commitment| {
+                    solution_commitment(solution).as_slice().ct_eq(commitment.as_slice()).into()
+                });
+                if !verified {
+                    return Err(TimelockError::CorruptedShard { index });
+                }
+                
+                let keystream = derive_keystream(locked.hash_algorithm, solution, locked.values.len());
+                let values: Zeroizing<Vec<u8>> = Zeroizing::new(locked.values.iter()
+                    .zip(keystream.iter())
+                    .map(|(&value, mask)| value ^ mask)
+                    .collect());
+                points.push((locked.x, values));
+            }
+            
+            let key_len = points[0].1.len();
+            if let Some(index) = points.iter().position(|(_, values)| values.len() != key_len) {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            
+            // Reconstruct every key byte as the value of its polynomial at x = 0
+            Ok(combine_bytes(&points))
+        }
+    }
+
+    impl SecretSplitter for LCS35 {
+        fn split(&self, key: &[u8], num_shards: usize, threshold: usize, rng: &mut dyn RngCore)
+            -> Result<Vec<Shard>, TimelockError>
+        {
+            self.shard(key, num_shards, threshold, rng)
+        }
+        
+        // Uses the threshold recorded in the shards
+        fn combine(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+            let threshold = shards.first().map_or(1, |shard| shard.threshold as usize);
+            self.unlock(shards, threshold)
+        }
+
+        // x, the hash byte, a modulus and base of at least a byte each behind
+        // their u16 lengths, and one locked key byte
+        fn min_payload_len(&self) -> usize {
+            9
+        }
+    }
+
+    impl Drop for LCS35 {
+        fn drop(&mut self) {
+            // BigUint has no Zeroize impl, so overwrite the trapdoor's limbs in
+            // place with zeros before its allocation is released
+            let digits = self.totient.bits().div_ceil(32) as usize;
+            self.totient.assign_from_slice(&vec![0u32; digits]);
         }
     }
 
     impl fmt::Debug for LCS35 {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(f, "LCS35 {{ difficulty: {}, iterations: {} }}", 
-                   self.difficulty, self.iterations)
+            write!(f, "LCS35 {{ difficulty: {}, iterations: {}, modulus_bits: {}, hash_algorithm: {:?} }}", 
+                   self.difficulty, self.iterations, self.modulus.bits(), self.hash_algorithm)
+        }
+    }
+
+    // Time `squarings` sequential modular squarings over a default-sized modulus
+    fn measure_squarings_per_second(squarings: u64) -> u64 {
+        // Squaring speed only depends on the operand size, so any odd modulus of
+        // the right length works and no primes need to be generated
+        let mut rng = rand::thread_rng();
+        let mut modulus = rng.gen_biguint(DEFAULT_MODULUS_BITS as u64);
+        modulus.set_bit(DEFAULT_MODULUS_BITS as u64 - 1, true);
+        modulus.set_bit(0, true);
+        let mut value = rng.gen_biguint_below(&modulus);
+
+        let start = Instant::now();
+        for _ in 0..squarings {
+            value = &value * &value % &modulus;
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        std::hint::black_box(value);
+
+        (squarings as f64 / elapsed.max(f64::EPSILON)) as u64
+    }
+
+    // As `measure_squarings_per_second`, squaring in batches until `duration` has passed
+    fn measure_squarings_for(duration: Duration) -> u64 {
+        const BATCH: u64 = 1000;
+        let mut rng = rand::thread_rng();
+        let mut modulus = rng.gen_biguint(DEFAULT_MODULUS_BITS as u64);
+        modulus.set_bit(DEFAULT_MODULUS_BITS as u64 - 1, true);
+        modulus.set_bit(0, true);
+        let mut value = rng.gen_biguint_below(&modulus);
+
+        let start = Instant::now();
+        let mut squarings = 0u64;
+        while start.elapsed() < duration {
+            for _ in 0..BATCH {
+                value = &value * &value % &modulus;
+            }
+            squarings += BATCH;
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        std::hint::black_box(value);
+
+        (squarings as f64 / elapsed.max(f64::EPSILON)) as u64
+    }
+
+    // A shard split into its x-coordinate, puzzle parameters and locked evaluations
+    struct LockedShard<'a> {
+        x: u8,
+        hash_algorithm: HashAlgorithm,
+        modulus: BigUint,
+        base: BigUint,
+        commitment: Option<[u8; COMMITMENT_LEN]>,
+        values: &'a [u8],
+    }
+
+    impl<'a> LockedShard<'a> {
+        fn parse(shard: &'a [u8]) -> Option<Self> {
+            let (&x, rest) = shard.split_first()?;
+            let (&hash_id, rest) = rest.split_first()?;
+            let hash_algorithm = HashAlgorithm::from_id(hash_id & !COMMITMENT_FLAG)?;
+            let (modulus, rest) = read_biguint(rest)?;
+            let (base, mut values) = read_biguint(rest)?;
+            let mut commitment = None;
+            if hash_id & COMMITMENT_FLAG != 0 {
+                let (stored, rest) = values.split_first_chunk::<COMMITMENT_LEN>()?;
+                commitment = Some(*stored);
+                values = rest;
+            }
+            if values.is_empty() {
+                return None;
+            }
+            Some(LockedShard { x, hash_algorithm, modulus, base, commitment, values })
+        }
+    }
+
+    // Payload laid out like an `LCS35` shard's, with a random odd modulus of the
+    // default size, base, commitment and `key_len` locked bytes. It locks nothing.
+    pub(crate) fn decoy_payload<R: RngCore + ?Sized>(x: u8, key_len: usize, rng: &mut R) -> Vec<u8> {
+        let mut modulus = rng.gen_biguint(DEFAULT_MODULUS_BITS as u64);
+        modulus.set_bit(DEFAULT_MODULUS_BITS as u64 - 1, true);
+        modulus.set_bit(0, true);
+        let modulus_bytes = modulus.to_bytes_le();
+        let base_bytes = rng.gen_biguint_below(&modulus).to_bytes_le();
+
+        let mut payload = Vec::with_capacity(6 + modulus_bytes.len() + base_bytes.len() + COMMITMENT_LEN + key_len);
+        payload.push(x);
+        payload.push(HashAlgorithm::default().id() | COMMITMENT_FLAG);
+        payload.extend_from_slice(&(modulus_bytes.len() as u16).to_le_bytes());
+        payload.extend_from_slice(&modulus_bytes);
+        payload.extend_from_slice(&(base_bytes.len() as u16).to_le_bytes());
+        payload.extend_from_slice(&base_bytes);
+        let random_start = payload.len();
+        payload.resize(random_start + COMMITMENT_LEN + key_len, 0);
+        rng.fill_bytes(&mut payload[random_start..]);
+        payload
+    }
+
+    // Modulus and base of the puzzle locking an `LCS35` shard payload
+    pub(crate) fn locked_puzzle(payload: &[u8]) -> Option<(BigUint, BigUint)> {
+        LockedShard::parse(payload).map(|locked| (locked.modulus, locked.base))
+    }
+
+    // Number of key bytes locked in an `LCS35` shard payload
+    pub(crate) fn locked_key_len(payload: &[u8]) -> Option<usize> {
+        LockedShard::parse(payload).map(|locked| locked.values.len())
+    }
+
+    // Read a u16 length-prefixed little-endian integer
+    fn read_biguint(bytes: &[u8]) -> Option<(BigUint, &[u8])> {
+        if bytes.len() < 2 {
+            return None;
+        }
+        let len = u16::from_le_bytes([bytes[0], bytes[1]]) as usize;
+        let rest = &bytes[2..];
+        if rest.len() < len {
+            return None;
+        }
+        Some((BigUint::from_bytes_le(&rest[..len]), &rest[len..]))
+    }
+
+    // Public commitment to a puzzle solution, domain-separated from the keystream
+    fn solution_commitment(solution: &BigUint) -> [u8; COMMITMENT_LEN] {
+        let solution_bytes = Zeroizing::new(solution.to_bytes_le());
+        let mut hasher = Sha256::new();
+        hasher.update(b"schrodinger lcs35 solution commitment");
+        hasher.update(&solution_bytes[..]);
+        hasher.finalize().into()
+    }
+
+    // Expand a puzzle solution into a keystream with the shard's hash algorithm
+    fn derive_keystream(hash: HashAlgorithm, solution: &BigUint, len: usize) -> Zeroizing<Vec<u8>> {
+        let solution_bytes = Zeroizing::new(solution.to_bytes_le());
+        hash.keystream(&solution_bytes, len)
+    }
+
+    fn generate_prime<R: Rng + ?Sized>(bits: usize, rng: &mut R) -> BigUint {
+        loop {
+            // Force the top two bits so p * q has the full requested length, and make it odd
+            let mut candidate = rng.gen_biguint(bits as u64);
+            candidate.set_bit(bits as u64 - 1, true);
+            candidate.set_bit(bits as u64 - 2, true);
+            candidate.set_bit(0, true);
+            if is_probable_prime(&candidate, rng) {
+                return candidate;
+            }
+        }
+    }
+
+    fn is_probable_prime<R: Rng + ?Sized>(n: &BigUint, rng: &mut R) -> bool {
+        for &small in SMALL_PRIMES.iter() {
+            let small = BigUint::from(small);
+            if *n == small {
+                return true;
+            }
+            if (n % &small).bits() == 0 {
+                return false;
+            }
+        }
+
+        // Write n - 1 as d * 2^s with d odd
+        let one = BigUint::one();
+        let two = BigUint::from(2u32);
+        let n_minus_one = n - &one;
+        let s = n_minus_one.trailing_zeros().unwrap_or(0);
+        let d = &n_minus_one >> s;
+
+        'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+            let a = rng.gen_biguint_range(&two, &n_minus_one);
+            let mut x = a.modpow(&d, n);
+            if x == one || x == n_minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = x.modpow(&two, n);
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
         }
+        true
     }
 }
 
-use silurian_puzzle::LCS35;
+#[cfg(feature = "std")]
+pub use silurian_puzzle::{LCS35, DEFAULT_CHECKPOINT_INTERVAL, DEFAULT_MODULUS_BITS, DEFAULT_PROGRESS_INTERVAL};
+
+// Default minimum entropy, in bits per byte, required of every shard
+#[cfg(feature = "std")]
+pub const DEFAULT_ENTROPY_THRESHOLD: f64 = 7.2;
+
+// Default largest deviation from the expected frequency allowed by the windowed
+// Borel regularity check and the runs test
+#[cfg(feature = "std")]
+pub const DEFAULT_BOREL_TOLERANCE: f64 = 0.05;
 
-#[derive(Debug)]
+// Default window size, in bits, of the Borel regularity check
+#[cfg(feature = "std")]
+pub const DEFAULT_BOREL_WINDOW_BITS: usize = 3;
+
+// Default shortest and longest keys `TimelockKeySharding` will shard
+#[cfg(feature = "std")]
+pub const DEFAULT_MIN_KEY_BYTES: usize = 16;
+#[cfg(feature = "std")]
+pub const DEFAULT_MAX_KEY_BYTES: usize = 4096;
+
+#[cfg(feature = "serde")]
+fn default_min_key_bytes() -> usize {
+    DEFAULT_MIN_KEY_BYTES
+}
+
+#[cfg(feature = "serde")]
+fn default_max_key_bytes() -> usize {
+    DEFAULT_MAX_KEY_BYTES
+}
+
+// Read size used by `TimelockKeySharding::shard_stream`
+#[cfg(feature = "std")]
+const STREAM_CHUNK_LEN: usize = 64 * 1024;
+
+// Entropy measurements for a single shard
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardMetrics {
+    pub index: usize,
+    pub shannon_entropy: f64,
+    pub min_entropy: f64,
+    pub collision_entropy: f64,
+    // Fraction of zero bits in the payload; 0.5 is perfectly balanced
+    pub borel_regularity_ratio: f64,
+    // Runs of identical bits per payload bit; about 0.5 for random data
+    pub runs_ratio: f64,
+    // Pearson's statistic of the byte counts against a uniform distribution
+    pub chi_squared_stat: f64,
+    // DFT of the payload bits, flagging periodic patterns
+    pub spectral: SpectralTestResult,
+    // Shortest LFSR generating the payload bits; about half the bit count for random data
+    pub linear_complexity: usize,
+    // Whether the configured entropy measure reached the entropy threshold
+    pub passed_entropy: bool,
+    pub passed_borel: bool,
+    pub passed_runs: bool,
+    pub chi_squared_passed: bool,
+    // Why the shard could not be read or decoded by `batch_entropy_check`; every
+    // check counts as failed then
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub read_error: Option<String>,
+}
+
+#[cfg(feature = "std")]
+impl ShardMetrics {
+    // Whether the shard passed every statistical check
+    pub fn passed(&self) -> bool {
+        self.passed_entropy && self.passed_borel && self.passed_runs && self.chi_squared_passed
+            && self.spectral.passed
+    }
+}
+
+// Outcome of `TimelockKeySharding::check_shard_entropy` across a shard set
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardEntropyReport {
+    pub passed: bool,
+    pub per_shard: Vec<ShardMetrics>,
+}
+
+// Clones share the splitter, audit log and rate limiter of the original
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimelockKeySharding {
     difficulty: u32,
     threshold: usize,
+    total_shards: Option<usize>,
+    entropy_threshold: f64,
+    entropy_measure: EntropyMeasure,
+    borel_tolerance: f64,
+    borel_window_bits: usize,
+    // Keystream hash for the puzzles of newly issued shards
+    #[cfg_attr(feature = "serde", serde(default))]
+    hash_algorithm: HashAlgorithm,
+    // Keys outside min_key_bytes..=max_key_bytes are refused before any sharding
+    #[cfg_attr(feature = "serde", serde(default = "default_min_key_bytes"))]
+    min_key_bytes: usize,
+    #[cfg_attr(feature = "serde", serde(default = "default_max_key_bytes"))]
+    max_key_bytes: usize,
+    // Scheme used to shard and reconstruct keys; `None` means timelocked Shamir
+    // sharing over a fresh `LCS35` puzzle for every call
+    #[cfg_attr(feature = "serde", serde(skip))]
+    splitter: Option<Arc<dyn SecretSplitter>>,
+    // Receives an event for every shard issued, reconstruction attempted and
+    // entropy check failed
+    #[cfg_attr(feature = "serde", serde(skip))]
+    audit_log: Option<Arc<dyn AuditLog>>,
+    // Refuses reconstruction after too many recent failures
+    #[cfg_attr(feature = "serde", serde(skip))]
+    rate_limiter: Option<ReconstructRateLimiter>,
 }
 
+#[cfg(feature = "std")]
 impl TimelockKeySharding {
-    pub fn new(difficulty: u32, threshold: usize) -> Self {
+    // `difficulty` is a plain `u32` or a `Difficulty`, e.g. `Difficulty::from_years`
+    pub fn new(difficulty: impl Into<Difficulty>, threshold: usize) -> Self {
         TimelockKeySharding {
-            difficulty,
+            difficulty: difficulty.into().get(),
             threshold,
+            total_shards: None,
+            entropy_threshold: DEFAULT_ENTROPY_THRESHOLD,
+            entropy_measure: EntropyMeasure::default(),
+            borel_tolerance: DEFAULT_BOREL_TOLERANCE,
+            borel_window_bits: DEFAULT_BOREL_WINDOW_BITS,
+            hash_algorithm: HashAlgorithm::default(),
+            min_key_bytes: DEFAULT_MIN_KEY_BYTES,
+            max_key_bytes: DEFAULT_MAX_KEY_BYTES,
+            splitter: None,
+            audit_log: None,
+            rate_limiter: None,
         }
     }
     
-    pub fn shard_key(&self, key: &str, num_shards: usize) -> Vec<String> {
-        // Create timelock puzzle with specified difficulty
-        let puzzle = LCS35::new(self.difficulty);
+    pub fn with_audit_log(mut self, audit_log: Arc<dyn AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+    
+    pub fn with_rate_limiter(mut self, rate_limiter: ReconstructRateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+    
+    // Swap in a different secret sharing scheme, e.g. `XorSplit` for n-of-n sharing
+    pub fn with_splitter(mut self, splitter: Box<dyn SecretSplitter>) -> Self {
+        self.splitter = Some(Arc::from(splitter));
+        self
+    }
+    
+    // Override the minimum Shannon entropy (bits/byte) each shard must reach
+    pub fn with_entropy_threshold(mut self, threshold: f64) -> Self {
+        self.entropy_threshold = threshold;
+        self
+    }
+    
+    // Choose which entropy estimate is compared against the entropy threshold
+    pub fn with_entropy_measure(mut self, measure: EntropyMeasure) -> Self {
+        self.entropy_measure = measure;
+        self
+    }
+    
+    // Override how far bit-pattern and run frequencies may stray before a shard is flagged
+    pub fn with_borel_tolerance(mut self, tolerance: f64) -> Self {
+        self.borel_tolerance = tolerance;
+        self
+    }
+    
+    // Lock new shards' puzzles with `hash` instead of SHA-256
+    pub fn with_hash_algorithm(mut self, hash: HashAlgorithm) -> Self {
+        self.hash_algorithm = hash;
+        self
+    }
+    
+    // Override the window size of the Borel regularity check; sizes outside
+    // 1-16 bits are clamped into that range
+    pub fn with_borel_window_bits(mut self, window_bits: usize) -> Self {
+        self.borel_window_bits = window_bits.clamp(1, 16);
+        self
+    }
+    
+    // Load a configuration from a TOML file; see `config.rs` for the format
+    #[cfg(feature = "toml")]
+    pub fn from_toml_config(path: &Path) -> Result<Self, TimelockError> {
+        config::load(path)
+    }
+    
+    #[cfg(feature = "toml")]
+    pub fn write_toml_config(&self, path: &Path) -> Result<(), TimelockError> {
+        config::save(self, path)
+    }
+    
+    // Each shard's puzzle takes 2^difficulty sequential squarings
+    pub fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+    
+    // Number of shards this configuration was validated against, if any
+    pub fn total_shards(&self) -> Option<usize> {
+        self.total_shards
+    }
+    
+    // Fails with `KeyTooShort` or `KeyTooLong` for a key outside the configured
+    // length limits, or `TooManyShards` above `MAX_SHARDS`, before any puzzle
+    // is created
+    pub fn shard_bytes(&self, key: &[u8], num_shards: usize) -> Result<Vec<Shard>, TimelockError> {
+        self.shard_key_with_rng(key, num_shards, &mut rand::thread_rng())
+    }
+    
+    // Shard with an explicit RNG, e.g. a seeded `ChaChaRng` for reproducible output.
+    // Fails if the key length is out of bounds or the configured splitter rejects
+    // the shard count or threshold.
+    pub fn shard_key_with_rng<R: RngCore>(&self, key: &[u8], num_shards: usize, rng: &mut R)
+        -> Result<Vec<Shard>, TimelockError>
+    {
+        self.check_key_len(key)?;
+        check_shard_count(num_shards)?;
+        let shards = if let Some(splitter) = &self.splitter {
+            splitter.split(key, num_shards, self.threshold, rng)?
+        } else {
+            // Create timelock puzzle with specified difficulty
+            let puzzle = self.new_puzzle(rng);
+            
+            // Shard the key
+            puzzle.shard(key, num_shards, self.threshold, rng)?
+        };
+        self.audit_shards_created(&shards);
+        Ok(shards)
+    }
+    
+    pub fn shard_key(&self, key: &str, num_shards: usize) -> Result<Vec<Shard>, TimelockError> {
+        self.shard_bytes(key.as_bytes(), num_shards)
+    }
+    
+    // Shard a fresh `key_len`-byte key drawn from `rng`, returning the key for
+    // immediate use. It is wiped when dropped, so copy it out only if necessary.
+    pub fn generate_and_shard(&self, key_len: usize, num_shards: usize, rng: &mut impl RngCore)
+        -> Result<(Zeroizing<Vec<u8>>, Vec<Shard>), TimelockError>
+    {
+        let mut key = Zeroizing::new(vec![0u8; key_len]);
+        rng.fill_bytes(&mut key);
+        let shards = self.shard_key_with_rng(&key, num_shards, rng)?;
+        Ok((key, shards))
+    }
+    
+    // As `shard_key_with_rng`, always with timelocked Shamir sharing, also
+    // returning the polynomial coefficients needed by `add_shard`. Store them
+    // sealed with `SealedCoefficients`, never in the clear.
+    pub fn shard_with_coefficients<R: RngCore>(&self, key: &[u8], num_shards: usize, rng: &mut R)
+        -> Result<(Vec<Shard>, Zeroizing<Vec<u8>>), TimelockError>
+    {
+        self.check_key_len(key)?;
+        check_shard_count(num_shards)?;
+        let puzzle = self.new_puzzle(rng);
+        let (shards, coefficients) = puzzle.shard_with_coefficients(key, num_shards, self.threshold, rng)?;
+        self.audit_shards_created(&shards);
+        Ok((shards, coefficients))
+    }
+    
+    // Issue a shard for a new custodian at x-coordinate `new_index` from the
+    // coefficients returned by `shard_with_coefficients`, without touching the
+    // other shards. `new_index` must lie past the original set's `total`, since
+    // every x-coordinate up to it has already been handed out. The new shard
+    // keeps the set's `total` and `threshold`, so it decodes and passes
+    // `verify_shard_set` alongside the shards dealt first.
+    pub fn add_shard(&self, existing_shards: &[Shard], new_index: u8, original_coefficients: &[u8])
+        -> Result<Shard, TimelockError>
+    {
+        let first = existing_shards.first()
+            .ok_or(TimelockError::InsufficientShards { provided: 0, required: 1 })?;
+        if new_index <= first.total {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "new shard index must be greater than {}, got {}", first.total, new_index)));
+        }
+        if existing_shards.iter().any(|shard| shard.payload.first() == Some(&new_index)) {
+            return Err(TimelockError::DuplicateShard { index: new_index });
+        }
         
-        // Shard the key
-        let shards = puzzle.shard(key.as_bytes(), num_shards);
+        let threshold = first.threshold as usize;
+        let key_len = silurian_puzzle::locked_key_len(&first.payload)
+            .ok_or(TimelockError::CorruptedShard { index: 0 })?;
+        if original_coefficients.len() != key_len * threshold {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "expected {} coefficients for a {}-byte key, got {}",
+                key_len * threshold, key_len, original_coefficients.len())));
+        }
         
-        // Convert to hex strings
-        shards.iter()
-            .map(|s| hex::encode(s))
-            .collect()
+        // The new shard gets a puzzle of its own, since the dealer no longer
+        // holds the trapdoor of the original one
+        let puzzle = LCS35::new(self.difficulty).with_hash_algorithm(self.hash_algorithm);
+        let shard = puzzle.issue_shard(new_index, first.total, first.threshold, original_coefficients,
+                                       &mut rand::thread_rng())?;
+        self.audit_shards_created(std::slice::from_ref(&shard));
+        Ok(shard)
     }
     
-    pub fn reconstruct_key(&self, shards: &[String]) -> Result<String, String> {
-        if shards.len() < self.threshold {
-            return Err(format!("Need at least {} shards, but only {} provided", 
-                               self.threshold, shards.len()));
+    // Proactively refresh a shard set for a new custodian set: recover the key
+    // from `old_shards` (solving their puzzles, if timelocked), then re-shard it
+    // at this configuration's difficulty with a fresh polynomial. The recovered
+    // key is wiped before returning.
+    pub fn rotate_shards<R: RngCore>(&self, old_shards: &[Shard], new_num_shards: usize, new_threshold: usize,
+                                     rng: &mut R) -> Result<Vec<Shard>, TimelockError> {
+        check_split_params(new_num_shards, new_threshold)?;
+        
+        let key = Zeroizing::new(match &self.splitter {
+            Some(splitter) => splitter.combine(old_shards)?,
+            None => LCS35::for_unlock(self.difficulty).combine(old_shards)?,
+        });
+        
+        let shards = match &self.splitter {
+            Some(splitter) => splitter.split(&key, new_num_shards, new_threshold, rng)?,
+            None => {
+                let puzzle = self.new_puzzle(rng);
+                puzzle.split(&key, new_num_shards, new_threshold, rng)?
+            }
+        };
+        self.audit_shards_created(&shards);
+        Ok(shards)
+    }
+    
+    // Shard `key` and hand every shard to `backend`, stopping at the first failed write
+    pub fn shard_and_store<B: StorageBackend>(&self, key: &[u8], backend: &B, num_shards: usize)
+        -> Result<(), TimelockError>
+    {
+        for shard in self.shard_bytes(key, num_shards)? {
+            backend.store_shard(&shard)?;
         }
+        Ok(())
+    }
+    
+    // Shard `key` into `output_dir`, creating it if needed, as one PEM file per
+    // shard named `shard_001.pem` onwards. On Unix only the owner can read them.
+    // If any file cannot be written, the ones already written are removed, and
+    // so are the directories this call created.
+    pub fn split_key_to_files<P: AsRef<Path>>(&self, key: &[u8], num_shards: usize, output_dir: P)
+        -> Result<Vec<PathBuf>, TimelockError>
+    {
+        let output_dir = output_dir.as_ref();
+        let shards = self.shard_bytes(key, num_shards)?;
+        // Deepest first, so each is empty by the time it is removed
+        let created: Vec<&Path> = output_dir.ancestors().take_while(|dir| !dir.exists()).collect();
+        fs::create_dir_all(output_dir)?;
         
-        // Convert hex strings back to bytes
-        let binary_shards: Result<Vec<Vec<u8>>, _> = shards.iter()
-            .map(|s| hex::decode(s))
-            .collect();
-            
-        match binary_shards {
-            Ok(binary_shards) => {
-                // Create timelock puzzle
-                let puzzle = LCS35::new(self.difficulty);
-                
-                // Attempt to unlock
-                match puzzle.unlock(&binary_shards, self.threshold) {
-                    Ok(key_bytes) => {
-                        // Try to convert to UTF-8 string
-                        match String::from_utf8(key_bytes) {
-                            Ok(key) => Ok(key),
-                            Err(_) => Err("Reconstructed key is not valid UTF-8".to_string()),
-                        }
-                    },
-                    Err(e) => Err(e),
+        let mut paths = Vec::with_capacity(shards.len());
+        for shard in &shards {
+            let path = output_dir.join(format!("shard_{:03}.pem", shard.index));
+            if let Err(e) = write_owner_only(&path, shard.to_pem().as_bytes()) {
+                for written in paths.iter().chain([&path]) {
+                    let _ = fs::remove_file(written);
+                }
+                for dir in &created {
+                    let _ = fs::remove_dir(dir);
                 }
-            },
-            Err(e) => Err(format!("Failed to decode hex: {}", e)),
+                return Err(e.into());
+            }
+            paths.push(path);
         }
+        Ok(paths)
     }
     
-    // Generate an entropy check for key shards
-    pub fn check_shard_entropy(&self, shards: &[String]) -> bool {
-        for shard in shards {
-            // Decode hex string
-            let binary = match hex::decode(shard) {
-                Ok(b) => b,
-                Err(_) => return false,
-            };
-            
-            // Check entropy (simplified)
-            let entropy = self.calculate_entropy(&binary);
-            if entropy < 7.2 {  // Minimum entropy threshold
-                return false;
+    // Shard the SHA-256 digest of everything read from `input`, hashed 64 KiB at
+    // a time so the input never has to fit in memory. Reconstruction recovers the
+    // 32-byte digest, not the input itself.
+    pub fn shard_stream<R: Read>(&self, mut input: R, num_shards: usize) -> Result<Vec<Shard>, TimelockError> {
+        let mut hasher = Sha256::new();
+        let mut buffer = Zeroizing::new(vec![0u8; STREAM_CHUNK_LEN]);
+        loop {
+            match input.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buffer[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
             }
-            
-            // Check for Borel regularity (simplified)
-            if self.check_borel_regularity(&binary) {
-                return false;  // Potential mathematical backdoor
+        }
+        
+        let digest = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+        self.shard_bytes(digest.as_slice(), num_shards)
+    }
+    
+    // Derive a key from `passphrase` and `salt` and shard it, returning the
+    // derived key alongside its shards for immediate use. Keep the salt: with
+    // the passphrase and `params` it derives the same key again.
+    pub fn shard_passphrase(&self, passphrase: &str, salt: &[u8; 32], num_shards: usize, params: Argon2Params)
+        -> Result<(Vec<Shard>, [u8; 32]), TimelockError>
+    {
+        params.check()?;
+        let mut key = derive_key_from_passphrase(passphrase, salt, params);
+        match self.shard_bytes(&key, num_shards) {
+            Ok(shards) => Ok((shards, key)),
+            Err(e) => {
+                key.zeroize();
+                Err(e)
             }
         }
+    }
+    
+    // With a rate limiter attached, fails with `RateLimited` once too many recent
+    // attempts have failed, and with `Io` if the attempt cannot be persisted
+    pub fn reconstruct_bytes(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        // Counted as failed until it succeeds, so parallel attempts share the limit
+        let reservation = match &self.rate_limiter {
+            Some(rate_limiter) => Some(rate_limiter.reserve()?),
+            None => None,
+        };
         
-        true
+        let result = self.reconstruct_unaudited(shards);
+        self.audit(AuditEvent::ReconstructionAttempted {
+            num_shards: shards.len(),
+            success: result.is_ok(),
+            timestamp: audit::now(),
+        });
+        if let (Ok(_), Some(rate_limiter), Some(reserved)) = (&result, &self.rate_limiter, reservation) {
+            rate_limiter.release(reserved);
+        }
+        result
     }
     
-    // Calculate Shannon entropy of data
-    fn calculate_entropy(&self, data: &[u8]) -> f64 {
-        let mut counts = [0u32; 256];
+    fn reconstruct_unaudited(&self, shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        if shards.len() < self.threshold {
+            return Err(TimelockError::InsufficientShards {
+                provided: shards.len(),
+                required: self.threshold,
+            });
+        }
         
-        // Count occurrences of each byte
-        for &byte in data {
-            counts[byte as usize] += 1;
+        // Truncated shards are refused before any puzzle is solved
+        let puzzle = LCS35::for_unlock(self.difficulty);
+        let splitter: &dyn SecretSplitter = self.splitter.as_deref().unwrap_or(&puzzle);
+        if let Some(index) = shards.iter().position(|shard| shard.payload.len() < splitter.min_payload_len()) {
+            return Err(TimelockError::CorruptedShard { index });
         }
         
-        // Calculate entropy
-        let len = data.len() as f64;
-        let mut entropy = 0.0;
+        if let Some(splitter) = &self.splitter {
+            return splitter.combine(shards);
+        }
         
-        for &count in counts.iter() {
-            if count > 0 {
-                let p = count as f64 / len;
-                entropy -= p * p.log2();
+        puzzle.unlock(shards, self.threshold)
+    }
+    
+    // `reconstruct_bytes` on tokio's blocking thread pool, so the squaring loop
+    // does not stall the executor thread awaiting it. A panic during
+    // reconstruction is resumed in the caller; a runtime shutting down before
+    // the task runs yields `Cancelled`.
+    #[cfg(feature = "async")]
+    pub async fn reconstruct_key_async(&self, shards: Vec<Shard>) -> Result<Vec<u8>, TimelockError> {
+        let sharding = self.clone();
+        match tokio::task::spawn_blocking(move || sharding.reconstruct_bytes(&shards)).await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(TimelockError::Cancelled),
+        }
+    }
+    
+    // Fetch one shard from each custodian URL concurrently with `fetch_shard`,
+    // using the token at the same position in `tokens`, then reconstruct. The
+    // puzzles are then solved on the calling thread; to keep them off the
+    // executor, fetch with `fetch_shard` and call `reconstruct_key_async`.
+    #[cfg(feature = "remote")]
+    pub async fn reconstruct_from_urls(&self, urls: &[&str], tokens: &[Option<&str>])
+        -> Result<Vec<u8>, TimelockError>
+    {
+        let shards = remote::fetch_shards(urls, tokens).await?;
+        self.reconstruct_bytes(&shards)
+    }
+    
+    // Inverse of `split_key_to_files`: read every `shard_*.pem` in `dir`, check
+    // them as a set and reconstruct from the `threshold` lowest-indexed ones, so
+    // the same directory always solves the same puzzles
+    pub fn reconstruct_from_directory<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<u8>, TimelockError> {
+        let mut shards = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_shard = path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("shard_") && name.ends_with(".pem"));
+            if is_shard {
+                shards.push(Shard::from_pem(&fs::read_to_string(&path)?)?);
             }
         }
         
-        entropy
+        if shards.len() < self.threshold {
+            return Err(TimelockError::InsufficientShards { provided: shards.len(), required: self.threshold });
+        }
+        verify_shard_set(&shards)?;
+        
+        shards.sort_by_key(|shard| shard.index);
+        shards.truncate(self.threshold);
+        self.reconstruct_bytes(&shards)
     }
     
-    // Check for Borel regularity (simplified)
-    // In a real implementation, this would be a more sophisticated test
-    fn check_borel_regularity(&self, data: &[u8]) -> bool {
-        // Count sequences of 0s and 1s at bit level
-        let mut zeros = 0;
-        let mut ones = 0;
+    pub fn reconstruct_key(&self, shards: &[Shard]) -> Result<String, TimelockError> {
+        let key_bytes = self.reconstruct_bytes(shards)?;
         
-        for &byte in data {
-            for i in 0..8 {
-                if (byte >> i) & 1 == 0 {
-                    zeros += 1;
-                } else {
-                    ones += 1;
+        // Try to convert to UTF-8 string
+        Ok(String::from_utf8(key_bytes)?)
+    }
+    
+    // Generate an entropy check for key shards
+    pub fn check_shard_entropy(&self, shards: &[Shard]) -> ShardEntropyReport {
+        let per_shard: Vec<ShardMetrics> = shards.iter()
+            .enumerate()
+            .map(|(index, shard)| {
+                // Biased bit patterns or runs in a shard are a potential
+                // mathematical backdoor
+                let (chi_squared_stat, chi_squared_passed) = entropy::chi_squared_uniformity(&shard.payload);
+                ShardMetrics {
+                    index,
+                    shannon_entropy: entropy::calculate_entropy(&shard.payload),
+                    min_entropy: entropy::calculate_min_entropy(&shard.payload),
+                    collision_entropy: entropy::calculate_collision_entropy(&shard.payload),
+                    borel_regularity_ratio: entropy::zero_bit_ratio(&shard.payload),
+                    runs_ratio: entropy::runs_ratio(&shard.payload),
+                    chi_squared_stat,
+                    spectral: entropy::spectral_test(&shard.payload),
+                    linear_complexity: entropy::linear_complexity(&entropy::to_bits(&shard.payload)),
+                    passed_entropy: self.entropy_measure.measure(&shard.payload) >= self.entropy_threshold,
+                    passed_borel: entropy::check_borel_regularity_windowed(
+                        &shard.payload, self.borel_window_bits, self.borel_tolerance),
+                    passed_runs: entropy::check_runs(&shard.payload, self.borel_tolerance),
+                    chi_squared_passed,
+                    read_error: None,
                 }
+            })
+            .collect();
+        
+        for (shard, metrics) in shards.iter().zip(&per_shard) {
+            if !metrics.passed() {
+                self.audit(AuditEvent::EntropyCheckFailed {
+                    index: shard.index,
+                    entropy: self.entropy_measure.measure(&shard.payload),
+                    timestamp: audit::now(),
+                });
             }
         }
         
-        // Check if distribution is too regular
-        // In a true random sequence, zeros and ones should be roughly equal
-        let total = zeros + ones;
-        let ratio = (zeros as f64) / (total as f64);
-        
-        // If ratio is too close to 0.5, it might indicate a backdoor
-        (ratio - 0.5).abs() < 0.01
+        ShardEntropyReport {
+            passed: per_shard.iter().all(ShardMetrics::passed),
+            per_shard,
+        }
     }
-}
-
-fn main() {
-    println!("Project Schrödinger - Timelock Key Sharding Demo");
     
-    // Create a key sharding system with:
-    // - difficulty level 10 (for demo - real system would use much higher)
-    // - threshold of 3 shards needed to reconstruct
-    let sharding = TimelockKeySharding::new(10, 3);
+    // TXT record strings carrying `shard`, for publishing it through DNS
+    pub fn shard_to_dns_txt(shard: &Shard) -> Vec<String> {
+        dns_txt::shard_to_dns_txt(shard)
+    }
     
-    // Generate a random key
-    let key = "supersecret_ai_model_encryption_key_2024";
-    println!("Original key: {}", key);
+    // Inverse of `shard_to_dns_txt`, given the record's strings in order
+    pub fn shard_from_dns_txt(records: &[&str]) -> Result<Shard, TimelockError> {
+        dns_txt::shard_from_dns_txt(records)
+    }
     
-    // Shard the key into 5 pieces
-    let shards = sharding.shard_key(key, 5);
-    println!("Generated {} shards:", shards.len());
+    // Convenience wrapper over `check_shard_entropy` for callers that only need pass/fail
+    pub fn check_shard_entropy_ok(&self, shards: &[Shard]) -> bool {
+        self.check_shard_entropy(shards).passed
+    }
     
-    for (i, shard) in shards.iter().enumerate() {
-        println!("Shard {}: {:.20}...", i + 1, shard);
+    fn check_key_len(&self, key: &[u8]) -> Result<(), TimelockError> {
+        if key.len() < self.min_key_bytes {
+            return Err(TimelockError::KeyTooShort { len: key.len(), min: self.min_key_bytes });
+        }
+        if key.len() > self.max_key_bytes {
+            return Err(TimelockError::KeyTooLong { len: key.len(), max: self.max_key_bytes });
+        }
+        Ok(())
     }
     
-    // Check entropy of shards
-    let entropy_check = sharding.check_shard_entropy(&shards);
-    println!("Shard entropy check: {}", if entropy_check { "PASSED" } else { "FAILED" });
+    fn new_puzzle<R: RngCore>(&self, rng: &mut R) -> LCS35 {
+        LCS35::generate_rsa_modulus_with_rng(self.difficulty, DEFAULT_MODULUS_BITS, rng)
+            .with_hash_algorithm(self.hash_algorithm)
+    }
     
-    // Demonstrate reconstruction (with 3 shards)
-    let subset = shards.iter().take(3).cloned().collect::<Vec<_>>();
-    match sharding.reconstruct_key(&subset) {
-        Ok(reconstructed) => {
-            println!("Key reconstruction successful!");
-            println!("Reconstructed key: {}", reconstructed);
-            println!("Key matches: {}", reconstructed == key);
-        },
-        Err(e) => {
-            println!("Key reconstruction failed: {}", e);
+    fn audit(&self, event: AuditEvent) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(event);
         }
     }
     
-    // Try with insufficient shards
-    let insufficient = shards.iter().take(2).cloned().collect::<Vec<_>>();
-    match sharding.reconstruct_key(&insufficient) {
-        Ok(_) => {
-            println!("WARNING: Key was reconstructed with insufficient shards!");
-        },
-        Err(e) => {
-            println!("Expected failure with insufficient shards: {}", e);
+    fn audit_shards_created(&self, shards: &[Shard]) {
+        let timestamp = audit::now();
+        for shard in shards {
+            self.audit(AuditEvent::ShardCreated { index: shard.index, timestamp });
         }
     }
-}
\ No newline at end of file
+}
+
+// Write `contents` to `path`, readable and writable only by the owner on Unix.
+// An existing file is truncated and its permissions narrowed first.
+#[cfg(feature = "std")]
+pub fn write_owner_only(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    // `mode` only applies to newly created files
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents)
+}
+
+// Validating builder for `TimelockKeySharding`
+//
+// Unlike `TimelockKeySharding::new`, `build` rejects configurations that would
+// silently disable the timelock or accept too few shards.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimelockKeyShardingBuilder {
+    difficulty: Option<u32>,
+    threshold: Option<usize>,
+    total_shards: Option<usize>,
+    entropy_threshold: Option<f64>,
+    entropy_measure: Option<EntropyMeasure>,
+    borel_tolerance: Option<f64>,
+    borel_window_bits: Option<usize>,
+    hash_algorithm: Option<HashAlgorithm>,
+    min_key_bytes: Option<usize>,
+    max_key_bytes: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl TimelockKeyShardingBuilder {
+    pub fn difficulty(mut self, difficulty: impl Into<Difficulty>) -> Self {
+        self.difficulty = Some(difficulty.into().get());
+        self
+    }
+
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    pub fn total_shards(mut self, total_shards: usize) -> Self {
+        self.total_shards = Some(total_shards);
+        self
+    }
+
+    pub fn entropy_threshold(mut self, entropy_threshold: f64) -> Self {
+        self.entropy_threshold = Some(entropy_threshold);
+        self
+    }
+
+    pub fn entropy_measure(mut self, entropy_measure: EntropyMeasure) -> Self {
+        self.entropy_measure = Some(entropy_measure);
+        self
+    }
+
+    pub fn borel_tolerance(mut self, borel_tolerance: f64) -> Self {
+        self.borel_tolerance = Some(borel_tolerance);
+        self
+    }
+
+    pub fn borel_window_bits(mut self, borel_window_bits: usize) -> Self {
+        self.borel_window_bits = Some(borel_window_bits);
+        self
+    }
+
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = Some(hash_algorithm);
+        self
+    }
+
+    pub fn min_key_bytes(mut self, min_key_bytes: usize) -> Self {
+        self.min_key_bytes = Some(min_key_bytes);
+        self
+    }
+
+    pub fn max_key_bytes(mut self, max_key_bytes: usize) -> Self {
+        self.max_key_bytes = Some(max_key_bytes);
+        self
+    }
+
+    pub fn build(self) -> Result<TimelockKeySharding, TimelockError> {
+        let difficulty = self.difficulty.ok_or_else(|| {
+            TimelockError::InvalidConfiguration("difficulty must be set".to_string())
+        })?;
+        let threshold = self.threshold.ok_or_else(|| {
+            TimelockError::InvalidConfiguration("threshold must be set".to_string())
+        })?;
+
+        // difficulty 0 gives a single squaring, i.e. no meaningful timelock
+        if difficulty < 1 {
+            return Err(TimelockError::InvalidConfiguration(
+                "difficulty must be at least 1".to_string()));
+        }
+        // 2^difficulty squarings must fit in a u64
+        if difficulty > MAX_DIFFICULTY {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "difficulty {} exceeds the maximum of {}", difficulty, MAX_DIFFICULTY)));
+        }
+        if threshold < 2 {
+            return Err(TimelockError::InvalidConfiguration(
+                "threshold must be at least 2".to_string()));
+        }
+        if let Some(total_shards) = self.total_shards {
+            if threshold > total_shards {
+                return Err(TimelockError::InvalidConfiguration(format!(
+                    "threshold {} exceeds total shard count {}", threshold, total_shards)));
+            }
+        }
+
+        // Shannon entropy of byte data can never exceed 8 bits/byte
+        let entropy_threshold = self.entropy_threshold.unwrap_or(DEFAULT_ENTROPY_THRESHOLD);
+        if !(0.0..=8.0).contains(&entropy_threshold) {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "entropy threshold {} is outside the range 0-8 bits/byte", entropy_threshold)));
+        }
+        let borel_tolerance = self.borel_tolerance.unwrap_or(DEFAULT_BOREL_TOLERANCE);
+        if !(0.0..=0.5).contains(&borel_tolerance) {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "Borel tolerance {} is outside the range 0-0.5", borel_tolerance)));
+        }
+        let borel_window_bits = self.borel_window_bits.unwrap_or(DEFAULT_BOREL_WINDOW_BITS);
+        if !(1..=16).contains(&borel_window_bits) {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "Borel window of {} bits is outside the range 1-16", borel_window_bits)));
+        }
+        let min_key_bytes = self.min_key_bytes.unwrap_or(DEFAULT_MIN_KEY_BYTES);
+        let max_key_bytes = self.max_key_bytes.unwrap_or(DEFAULT_MAX_KEY_BYTES);
+        if min_key_bytes < 1 || min_key_bytes > max_key_bytes {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "key length limits {}-{} bytes must be at least 1 and in order", min_key_bytes, max_key_bytes)));
+        }
+
+        Ok(TimelockKeySharding {
+            difficulty,
+            threshold,
+            total_shards: self.total_shards,
+            entropy_threshold,
+            entropy_measure: self.entropy_measure.unwrap_or_default(),
+            borel_tolerance,
+            borel_window_bits,
+            hash_algorithm: self.hash_algorithm.unwrap_or_default(),
+            min_key_bytes,
+            max_key_bytes,
+            splitter: None,
+            audit_log: None,
+            rate_limiter: None,
+        })
+    }
+}
@@ -0,0 +1,218 @@
+// Feldman Verifiable Secret Sharing
+//
+// Shamir shares are computed over Z_q, where p = 2q + 1 is the RFC 3526 2048-bit
+// safe prime, and every polynomial coefficient a_j is published as the commitment
+// g^a_j mod p. A shard holder with share y at x can then check
+// g^y == prod_j C_j^(x^j) mod p without seeing any other shard.
+//
+// Unlike `LCS35` shards, VSS shards are not timelocked.
+
+use crate::error::TimelockError;
+use crate::secret_splitter::check_split_params;
+use crate::shard::Shard;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::RngCore;
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+
+// RFC 3526 group 14 prime; 2 generates its subgroup of prime order q = (p - 1) / 2
+const MODP_2048_PRIME_HEX: &str = concat!(
+    "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74",
+    "020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F1437",
+    "4FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7ED",
+    "EE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF05",
+    "98DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB",
+    "9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3B",
+    "E39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF695581718",
+    "3995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF",
+);
+const GENERATOR: u32 = 2;
+
+// Key bytes packed into each secret; 255 bytes always fit below the 2047-bit q
+const CHUNK_BYTES: usize = 255;
+
+// Width of one serialized share value, enough for any element of Z_q
+const VALUE_BYTES: usize = 256;
+
+// Commitments g^a_j mod p to the degree-j coefficient of every key chunk's polynomial
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment {
+    pub values: Vec<BigUint>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeldmanVSS;
+
+impl FeldmanVSS {
+    // Split `key` into `num_shards` shards with the given threshold, returning
+    // the shards and one commitment per polynomial degree
+    pub fn commit_shards(key: &[u8], num_shards: usize, threshold: usize, rng: &mut impl RngCore)
+        -> Result<(Vec<Shard>, Vec<Commitment>), TimelockError>
+    {
+        check_split_params(num_shards, threshold)?;
+
+        let (p, q, g) = group();
+
+        // One polynomial over Z_q per key chunk, with the chunk as constant term
+        let polynomials: Vec<Vec<BigUint>> = key.chunks(CHUNK_BYTES)
+            .map(|chunk| {
+                let mut coefficients = vec![BigUint::from_bytes_be(chunk)];
+                for _ in 1..threshold {
+                    coefficients.push(rng.gen_biguint_below(q));
+                }
+                coefficients
+            })
+            .collect();
+
+        let commitments = (0..threshold)
+            .map(|degree| Commitment {
+                values: polynomials.iter()
+                    .map(|coefficients| g.modpow(&coefficients[degree], p))
+                    .collect(),
+            })
+            .collect();
+
+        let shards = (1..=num_shards)
+            .map(|i| {
+                let x = BigUint::from(i);
+
+                // Payload layout: [x, key_len (u32 LE), y_0, y_1, ...] with each y
+                // a VALUE_BYTES-wide big-endian element of Z_q
+                let mut payload = Vec::with_capacity(5 + polynomials.len() * VALUE_BYTES);
+                payload.push(i as u8);
+                payload.extend_from_slice(&(key.len() as u32).to_le_bytes());
+                for coefficients in &polynomials {
+                    let y = eval_poly(coefficients, &x, q);
+                    payload.extend_from_slice(&to_fixed_width(&y));
+                }
+
+                Shard::new(i as u8, num_shards as u8, threshold as u8, payload)
+            })
+            .collect();
+
+        Ok((shards, commitments))
+    }
+
+    // Check a single shard against the published commitments
+    pub fn verify_shard(shard: &Shard, commitments: &[Commitment]) -> bool {
+        let Some((x, _, values)) = parse_payload(shard) else {
+            return false;
+        };
+        if values.is_empty() || commitments.iter().any(|c| c.values.len() != values.len()) {
+            return false;
+        }
+
+        let (p, q, g) = group();
+        let x = BigUint::from(x);
+
+        values.iter().enumerate().all(|(chunk, y)| {
+            if y >= q {
+                return false;
+            }
+
+            // prod_j C_j^(x^j) mod p, with x^j reduced mod q since every C_j has order q
+            let mut expected = BigUint::one();
+            let mut x_power = BigUint::one();
+            for commitment in commitments {
+                expected = expected * commitment.values[chunk].modpow(&x_power, p) % p;
+                x_power = x_power * &x % q;
+            }
+
+            g.modpow(y, p) == expected
+        })
+    }
+
+    // Recover the key from at least `threshold` VSS shards by Lagrange interpolation over Z_q
+    pub fn reconstruct(shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        let required = shards.first().map_or(1, |s| s.threshold.max(1) as usize);
+        if shards.len() < required {
+            return Err(TimelockError::InsufficientShards { provided: shards.len(), required });
+        }
+
+        let mut points = Vec::with_capacity(required);
+        let mut key_len = None;
+        for (index, shard) in shards.iter().take(required).enumerate() {
+            let (x, len, values) = parse_payload(shard).ok_or(TimelockError::CorruptedShard { index })?;
+            if x == 0 || *key_len.get_or_insert(len) != len || values.len() != len.div_ceil(CHUNK_BYTES) {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+            if points.iter().any(|(other, _)| *other == x) {
+                return Err(TimelockError::DuplicateShard { index: x });
+            }
+            points.push((x, values));
+        }
+
+        let (_, q, _) = group();
+        let key_len = key_len.unwrap_or(0);
+        let mut key = Vec::with_capacity(key_len);
+        for chunk in 0..key_len.div_ceil(CHUNK_BYTES) {
+            let chunk_len = CHUNK_BYTES.min(key_len - chunk * CHUNK_BYTES);
+            let secret = interpolate_at_zero(&points, chunk, q);
+            let bytes = Zeroizing::new(secret.to_bytes_be());
+            if bytes.len() > chunk_len {
+                return Err(TimelockError::PuzzleError(
+                    "reconstructed chunk does not fit the recorded key length".to_string()));
+            }
+            key.resize(key.len() + chunk_len - bytes.len(), 0);
+            key.extend_from_slice(&bytes);
+        }
+
+        Ok(key)
+    }
+}
+
+// (p, q, g) for the commitment group, parsed on first use
+fn group() -> &'static (BigUint, BigUint, BigUint) {
+    static GROUP: OnceLock<(BigUint, BigUint, BigUint)> = OnceLock::new();
+    GROUP.get_or_init(|| {
+        let p = BigUint::parse_bytes(MODP_2048_PRIME_HEX.as_bytes(), 16)
+            .expect("MODP prime constant is valid hex");
+        let q = (&p - BigUint::one()) >> 1;
+        (p, q, BigUint::from(GENERATOR))
+    })
+}
+
+fn eval_poly(coefficients: &[BigUint], x: &BigUint, q: &BigUint) -> BigUint {
+    coefficients.iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, coefficient| (acc * x + coefficient) % q)
+}
+
+// Lagrange interpolation at x = 0 of one chunk's shares over Z_q
+fn interpolate_at_zero(points: &[(u8, Vec<BigUint>)], chunk: usize, q: &BigUint) -> BigUint {
+    let q_minus_two = q - BigUint::from(2u32);
+    let mut secret = BigUint::zero();
+    for (i, (x_i, values)) in points.iter().enumerate() {
+        let mut numerator = BigUint::one();
+        let mut denominator = BigUint::one();
+        for (j, (x_j, _)) in points.iter().enumerate() {
+            if i != j {
+                numerator = numerator * BigUint::from(*x_j) % q;
+                denominator = denominator * ((BigUint::from(*x_j) + q - BigUint::from(*x_i)) % q) % q;
+            }
+        }
+        // q is prime, so the inverse is denominator^(q - 2)
+        let basis = numerator * denominator.modpow(&q_minus_two, q) % q;
+        secret = (secret + &values[chunk] * basis) % q;
+    }
+    secret
+}
+
+fn to_fixed_width(value: &BigUint) -> Zeroizing<Vec<u8>> {
+    let bytes = Zeroizing::new(value.to_bytes_be());
+    let mut fixed = Zeroizing::new(vec![0u8; VALUE_BYTES - bytes.len()]);
+    fixed.extend_from_slice(&bytes);
+    fixed
+}
+
+// Split a payload into (x, key length, share values)
+fn parse_payload(shard: &Shard) -> Option<(u8, usize, Vec<BigUint>)> {
+    let (&x, rest) = shard.payload.split_first()?;
+    let key_len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+    let values = &rest[4..];
+    if values.len() % VALUE_BYTES != 0 {
+        return None;
+    }
+    Some((x, key_len, values.chunks(VALUE_BYTES).map(BigUint::from_bytes_be).collect()))
+}
@@ -0,0 +1,51 @@
+// wasm-bindgen exports for sharding keys in the browser, so a key ceremony never
+// has to send the key over the network
+//
+// Shards cross the boundary as arrays of `Shard::to_hex` strings. Randomness
+// comes from the browser's crypto.getRandomValues through getrandom's `js`
+// backend, which the wasm32 build enables.
+
+use crate::secret_splitter::check_split_params;
+use crate::shard::Shard;
+use crate::TimelockKeySharding;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+// Throws a JS error if the shard count, threshold or key length is out of range
+#[wasm_bindgen]
+pub fn shard_key_wasm(key: &str, num_shards: usize, threshold: usize, difficulty: u32) -> JsValue {
+    if let Err(e) = check_split_params(num_shards, threshold) {
+        wasm_bindgen::throw_str(&e.to_string());
+    }
+
+    TimelockKeySharding::new(difficulty, threshold)
+        .shard_key(key, num_shards)
+        .unwrap_or_else(|e| wasm_bindgen::throw_str(&e.to_string()))
+        .iter()
+        .map(|shard| JsValue::from_str(&shard.to_hex()))
+        .collect::<js_sys::Array>()
+        .into()
+}
+
+// `shards` must be an array of hex strings as returned by `shard_key_wasm`
+#[wasm_bindgen]
+pub fn reconstruct_key_wasm(shards: JsValue, threshold: usize, difficulty: u32) -> Result<String, JsValue> {
+    let array = shards.dyn_into::<js_sys::Array>()
+        .map_err(|_| JsValue::from_str("shards must be an array of hex strings"))?;
+    let shards = array.iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let hex = value.as_string()
+                .ok_or_else(|| JsValue::from_str(&format!("shard {} is not a string", index)))?;
+            Shard::from_hex(&hex).map_err(to_js_error)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    TimelockKeySharding::new(difficulty, threshold)
+        .reconstruct_key(&shards)
+        .map_err(to_js_error)
+}
+
+fn to_js_error(e: crate::error::TimelockError) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
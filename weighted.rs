@@ -0,0 +1,108 @@
+// Weighted threshold sharing: a custodian of weight w holds w Shamir shares, so
+// their shard counts w votes toward the threshold
+//
+// Shares are plain GF(256) Shamir shares as in `ShamirSplit`, not timelocked.
+// Each custodian gets one shard whose index is their custodian id and whose
+// total and threshold count votes, so `Shard::validate` accepts it.
+// Payload layout: [custodian_id, weight, then per share x, y_0, y_1, ...]
+
+use crate::error::TimelockError;
+use crate::secret_splitter::{combine_bytes, share_bytes};
+use crate::shard::Shard;
+use rand::RngCore;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WeightedSharding;
+
+impl WeightedSharding {
+    // `weights` pairs each custodian id with their vote count; any custodians
+    // whose weights add up to `threshold` can reconstruct the key
+    pub fn shard_weighted(key: &[u8], weights: &[(u8, usize)], threshold: usize, rng: &mut impl RngCore)
+        -> Result<Vec<Shard>, TimelockError>
+    {
+        let total_weight: usize = weights.iter().map(|&(_, weight)| weight).sum();
+        if weights.len() < 2 || weights.len() > u8::MAX as usize {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "number of custodians must be between 2 and 255, got {}", weights.len())));
+        }
+        if total_weight > u8::MAX as usize {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "custodian weights must add up to at most 255, got {}", total_weight)));
+        }
+        if threshold < 2 || threshold > total_weight {
+            return Err(TimelockError::InvalidConfiguration(format!(
+                "threshold must be between 2 and the total weight {}, got {}", total_weight, threshold)));
+        }
+        let mut seen = HashSet::with_capacity(weights.len());
+        for &(custodian, weight) in weights {
+            if custodian == 0 || weight == 0 {
+                return Err(TimelockError::InvalidConfiguration(format!(
+                    "custodian {} needs a nonzero id and weight, got weight {}", custodian, weight)));
+            }
+            if !seen.insert(custodian) {
+                return Err(TimelockError::DuplicateShard { index: custodian });
+            }
+        }
+
+        let values = share_bytes(key, total_weight, threshold, rng);
+
+        // Custodians take consecutive runs of x-coordinates starting at 1
+        let mut next_x = 1u8;
+        Ok(weights.iter()
+            .map(|&(custodian, weight)| {
+                let mut payload = Vec::with_capacity(2 + weight * (1 + key.len()));
+                payload.push(custodian);
+                payload.push(weight as u8);
+                for _ in 0..weight {
+                    payload.push(next_x);
+                    payload.extend_from_slice(&values[next_x as usize - 1]);
+                    next_x = next_x.wrapping_add(1);
+                }
+                Shard::new(custodian, total_weight as u8, threshold as u8, payload)
+            })
+            .collect())
+    }
+
+    // Pool the shares of every supplied custodian until their weight reaches the
+    // threshold recorded in the shards
+    pub fn reconstruct_weighted(shards: &[Shard]) -> Result<Vec<u8>, TimelockError> {
+        let required = shards.first().map_or(1, |shard| (shard.threshold as usize).max(1));
+
+        let mut custodians = HashSet::with_capacity(shards.len());
+        let mut xs = HashSet::with_capacity(required);
+        let mut shares: Vec<(u8, &[u8])> = Vec::with_capacity(required);
+        for (index, shard) in shards.iter().enumerate() {
+            if shares.len() >= required {
+                break;
+            }
+            let [custodian, weight, rest @ ..] = shard.payload.as_slice() else {
+                return Err(TimelockError::CorruptedShard { index });
+            };
+            if !custodians.insert(*custodian) {
+                return Err(TimelockError::DuplicateShard { index: *custodian });
+            }
+            let weight = *weight as usize;
+            if weight == 0 || !rest.len().is_multiple_of(weight) || rest.len() / weight < 1 {
+                return Err(TimelockError::CorruptedShard { index });
+            }
+
+            for share in rest.chunks(rest.len() / weight) {
+                let (&x, values) = share.split_first().ok_or(TimelockError::CorruptedShard { index })?;
+                if x == 0 || shares.first().is_some_and(|(_, first)| first.len() != values.len()) {
+                    return Err(TimelockError::CorruptedShard { index });
+                }
+                if !xs.insert(x) {
+                    return Err(TimelockError::DuplicateShard { index: x });
+                }
+                shares.push((x, values));
+            }
+        }
+
+        if shares.len() < required {
+            return Err(TimelockError::InsufficientShards { provided: shares.len(), required });
+        }
+
+        Ok(combine_bytes(&shares[..required]))
+    }
+}